@@ -0,0 +1,61 @@
+// https://www.hackerrank.com/challenges/botclean
+extern crate hackerrank;
+
+use hackerrank::bot::{self, Action, BoardView, Bot, Size};
+use hackerrank::grid::{Cell, Connectivity};
+
+/// Cleans whatever it's standing on, otherwise heads for the nearest dirt it
+/// can reach, going around any walls.
+struct Cleaner;
+
+impl Bot for Cleaner {
+    fn next_move(&mut self, view: &BoardView) -> Action {
+        let here = view.position();
+        let board = view.board();
+        if board[here] == Cell::Dirty {
+            return Action::Clean;
+        }
+        board.positions(|c| c == Cell::Dirty)
+            .into_iter()
+            .filter_map(|dirt| board.bfs(here, dirt, Connectivity::Four, |c| c != Cell::Wall))
+            .min_by_key(|route| route.len())
+            .and_then(|route| route.first().cloned())
+            .map_or(Action::Stay, Action::from)
+    }
+}
+
+#[cfg_attr(test, allow(dead_code))]
+fn main() {
+    bot::play(Cleaner, Size::Fixed(5));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cleaner;
+    use hackerrank::Scanner;
+    use hackerrank::bot::{Action, BoardView, Bot, Size};
+    use hackerrank::grid::Direction;
+
+    fn next_move(input: &str) -> Action {
+        let view = BoardView::read(&mut Scanner::new(input.as_bytes()), Size::Fixed(5)).unwrap();
+        Cleaner.next_move(&view)
+    }
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(next_move("0 0\nb---d\n-d--d\n--dd-\n--d--\n----d\n"), Action::Move(Direction::Right));
+    }
+
+    #[test]
+    fn test_clean_and_done() {
+        assert_eq!(next_move("1 1\n-----\n-d---\n-----\n-----\n----d\n"), Action::Clean);
+        assert_eq!(next_move("4 4\n-----\n-----\n-----\n-----\n----b\n"), Action::Stay);
+        assert_eq!(next_move("4 4\n-----\n-----\n-----\nd----\n----b\n"), Action::Move(Direction::Up));
+    }
+
+    #[test]
+    fn test_walls() {
+        assert_eq!(next_move("0 0\nb#d--\n-#---\n-----\n-----\n-----\n"), Action::Move(Direction::Down));
+        assert_eq!(next_move("0 0\nb#d--\n-#---\n-#---\n-#---\n-#--d\n"), Action::Stay);
+    }
+}
@@ -1,270 +1,73 @@
-use std::{io, fmt, iter, ops, str};
-
-#[derive(Debug, PartialEq, Clone, Copy)]
-struct Coord {
-    x: i32,
-    y: i32,
-}
-
-impl Coord {
-    fn new(x: i32, y: i32) -> Coord {
-        Coord { x: x, y: y }
-    }
-}
-
-impl ops::Sub<Coord> for Coord {
-    type Output = Coord;
-    fn sub(self, rhs: Coord) -> Coord {
-        Coord::new(self.x - rhs.x, self.y - rhs.y)
-    }
-}
-
-#[derive(Debug, PartialEq, Clone)]
-struct Board {
-    width: usize,
-    height: usize,
-    cells: Vec<Vec<Cell>>,
-}
-
-impl Board {
-    fn new(width: usize, height: usize) -> Board {
-        let cells: Vec<Vec<Cell>> =
-            iter::repeat(
-                iter::repeat(Cell::Empty)
-                .take(width)
-                .collect())
-            .take(height)
-            .collect();
-
-        Board {
-            width: width,
-            height: height,
-            cells: cells,
-        }
-    }
-
-    fn iter(&self) -> BoardIterator {
-        BoardIterator::new(self)
-    }
-
-    fn position<P>(&self, predicate: P) -> Option<Coord>
-        where P: Fn(Cell) -> bool
-    {
-        for (idx, cell) in self {
-            if predicate(cell) {
-                return Some(idx);
-            }
-        }
-        None
-    }
-
-    fn path(&self, pos1: Coord, pos2: Coord) -> Vec<Direction> {
-        let delta = pos1 - pos2;
-        let horiz = if delta.x > 0 { Direction::Left } else { Direction::Right };
-        let vert = if delta.y > 0 { Direction::Up } else { Direction::Down };
-
-        let mut path = vec![];
-        for _ in 0..delta.x.abs() {
-            path.push(horiz);
-        }
-        for _ in 0..delta.y.abs() {
-            path.push(vert);
-        }
-
-        path
-    }
-}
-
-impl ops::Index<Coord> for Board {
-    type Output = Cell;
-
-    fn index(&self, c: Coord) -> &Cell {
-        self.cells.index(c.y as usize).index(c.x as usize)
-    }
-}
-
-impl ops::IndexMut<Coord> for Board {
-    fn index_mut(&mut self, c: Coord) -> &mut Cell {
-        self.cells.index_mut(c.y as usize).index_mut(c.x as usize)
-    }
-}
-
-impl str::FromStr for Board {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Board, ()> {
-        let lines: Vec<&str> = s.split('\n').filter(|l| l.trim().len() > 0).collect();
-        let width = lines[0].len();
-        let height = lines.len();
-
-        let mut board = Board::new(width, height);
-
-        for (y, l) in lines.iter().enumerate() {
-            assert_eq!(l.len(), width);
-            for (x, c) in l.chars().enumerate() {
-                board[Coord::new(x as i32, y as i32)] = match c {
-                    '-' => Cell::Empty,
-                    'p' => Cell::Princess,
-                    'm' => Cell::Bot,
-                    _ => panic!(format!("Unknown cell: '{:?}'.", c)),
-                }
-            }
-        }
-
-        Ok(board)
+extern crate hackerrank;
+
+use hackerrank::InputError;
+use hackerrank::grid::{Board, Cell, Direction, Heuristic};
+
+/// A board that parsed but can't be played. Nothing in it is the wrong
+/// token, so there's only what was wanted and why.
+fn unplayable(expected: &str, reason: &str) -> InputError {
+    InputError {
+        line: None,
+        column: None,
+        token: None,
+        expected: Some(expected.to_string()),
+        reason: Some(reason.to_string()),
     }
 }
 
-impl<'a> iter::IntoIterator for &'a Board {
-    type Item = (Coord, Cell);
-    type IntoIter = BoardIterator<'a>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
-    }
-}
-
-struct BoardIterator<'a> {
-    board: &'a Board,
-    x: i32,
-    y: i32,
-    done: bool,
-}
-
-impl<'a> BoardIterator<'a> {
-    fn new(board: &'a Board) -> BoardIterator<'a> {
-        BoardIterator { board: board, x: 0, y: 0, done: false }
-    }
+/// The way from the bot to the nearest princess. Errors are positioned
+/// within the board.
+fn find_path(board: &Board) -> Result<Vec<Direction>, InputError> {
+    let bots = board.positions(|c| c == Cell::Bot);
+    let bot_position = match bots[..] {
+        [] => return Err(unplayable("one bot ('m')", "the board has none")),
+        [bot] => bot,
+        [_, second, ..] => {
+            return Err(unplayable("one bot ('m')", "found a second one")
+                       .at(second.y as usize + 1, second.x as usize + 1));
+        },
+    };
+    let princess_position = board.positions(|c| c == Cell::Princess)
+        .into_iter()
+        .min_by_key(|&p| Heuristic::Manhattan.estimate(bot_position, p))
+        .ok_or_else(|| unplayable("a princess ('p')", "the board has none"))?;
+    Ok(board.path(bot_position, princess_position))
 }
 
-impl<'a> Iterator for BoardIterator<'a> {
-    type Item = (Coord, Cell);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.done {
-            None
-        } else {
-            let idx = Coord::new(self.x, self.y);
-            let res = (idx, self.board[idx]);
-            self.x += 1;
-            if self.x as usize >= self.board.width {
-                self.x = 0;
-                self.y += 1;
-            }
-            if self.y as usize >= self.board.height {
-                self.done = true;
-            }
-            Some(res)
+fn main() {
+    hackerrank::run(|scan| {
+        let size = scan.read()?;
+        let board: Board = scan.block(size)?;
+        // The board starts on the second line.
+        let path = find_path(&board).map_err(|err| err.offset(2, 1))?;
+        for dir in path {
+            println!("{}", dir);
         }
-    }
-}
-
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Cell {
-    Empty,
-    Princess,
-    Bot,
-}
-
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-impl fmt::Display for Direction {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        try!(fmt.write_str(match *self {
-            Direction::Up => "UP",
-            Direction::Right => "RIGHT",
-            Direction::Down => "DOWN",
-            Direction::Left => "LEFT",
-        }));
         Ok(())
-    }
-}
-
-fn read_line<T>() -> T
-    where T: str::FromStr + fmt::Debug,
-          T::Err: fmt::Debug,
-{
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    input.trim().parse().unwrap()
-}
-
-fn read_lines<T>() -> T
-    where T: str::FromStr + fmt::Debug,
-          T::Err: fmt::Debug,
-{
-    let size: usize = read_line();
-    let mut acc = String::new();
-    for _ in 0..size {
-        let line: String = read_line();
-        acc = acc + &line[..] + "\n";
-    }
-    acc.parse().unwrap()
-}
-
-fn find_path(board: &Board) -> Vec<Direction> {
-    let princess_position = board.position(|c| c == Cell::Princess).unwrap();
-    let bot_position = board.position(|c| c == Cell::Bot).unwrap();
-    board.path(princess_position, bot_position)
-}
-
-fn main() {
-    let board: Board = read_lines();
-    let path = find_path(&board);
-    for dir in path {
-        println!("{}", dir);
-    }
+    });
 }
 
 
 #[test]
 fn test_sample() {
-    let board = Board { width: 3, height: 3, cells: vec![
-        vec![Cell::Princess, Cell::Empty, Cell::Empty],
-        vec![Cell::Empty, Cell::Bot, Cell::Empty],
-        vec![Cell::Empty, Cell::Empty, Cell::Empty],
-    ]};
-    assert_eq!(find_path(&board), vec![Direction::Right, Direction::Down]);
+    let board: Board = "p--\n-m-\n---".parse().unwrap();
+    assert_eq!(find_path(&board), Ok(vec![Direction::Left, Direction::Up]));
 }
 
 #[test]
-fn test_indexing() {
-    let mut board = Board::new(3, 3);
-    assert_eq!(board[Coord::new(1, 2)], Cell::Empty);
-    board[Coord::new(1, 2)] = Cell::Princess;
-    assert_eq!(board[Coord::new(1, 2)], Cell::Princess);
+fn test_nearest_princess() {
+    let board: Board = "p---\n----\n--m-\n---p".parse().unwrap();
+    assert_eq!(find_path(&board), Ok(vec![Direction::Right, Direction::Down]));
 }
 
 #[test]
-fn test_from_str() {
-    let input = "\
-        p--\n\
-        -m-\n\
-        ---\n\
-    ";
-    let actual: Board = input.parse().unwrap();
-    let mut expected = Board::new(3, 3);
-    expected[Coord::new(0, 0)] = Cell::Princess;
-    expected[Coord::new(1, 1)] = Cell::Bot;
-
-    assert_eq!(actual, expected);
-}
-
-#[test]
-fn test_position() {
-    let input = vec![
-        "---",
-        "--m",
-        "p--",
-    ].join("\n");
-    let board: Board = input.parse().unwrap();
-
-    assert_eq!(board[Coord::new(2, 1)], Cell::Bot);
-    assert_eq!(board.position(|c| c == Cell::Princess), Some(Coord::new(0, 2)));
+fn test_missing_pieces() {
+    let err = find_path(&"---\n-m-\n---".parse().unwrap()).unwrap_err();
+    assert_eq!(err.expected, Some("a princess ('p')".to_string()));
+    assert_eq!(err.token, None);
+    let err = find_path(&"p-m\n---\n-m-".parse().unwrap()).unwrap_err();
+    assert_eq!((err.line, err.column, &err.token), (Some(3), Some(2), &None));
+    assert_eq!(err.to_string(), "line 3, column 2: expected one bot ('m'): found a second one");
+    let err = find_path(&"p--\n---".parse().unwrap()).unwrap_err();
+    assert_eq!(err.reason, Some("the board has none".to_string()));
 }
@@ -0,0 +1,56 @@
+// https://www.hackerrank.com/challenges/saveprincess2
+extern crate hackerrank;
+
+use hackerrank::bot::{self, Action, BoardView, Bot, Size};
+use hackerrank::grid::{Cell, Connectivity};
+
+/// Heads for the nearest princess it can reach, one step per run.
+struct Rescuer;
+
+impl Bot for Rescuer {
+    fn next_move(&mut self, view: &BoardView) -> Action {
+        let here = view.position();
+        let board = view.board();
+        board.positions(|c| c == Cell::Princess)
+            .into_iter()
+            .filter_map(|princess| board.bfs(here, princess, Connectivity::Four, |c| c != Cell::Wall))
+            .min_by_key(|route| route.len())
+            .and_then(|route| route.first().cloned())
+            .map_or(Action::Stay, Action::from)
+    }
+}
+
+#[cfg_attr(test, allow(dead_code))]
+fn main() {
+    bot::play(Rescuer, Size::Leading);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rescuer;
+    use hackerrank::Scanner;
+    use hackerrank::bot::{Action, BoardView, Bot, Size};
+    use hackerrank::grid::Direction;
+
+    fn next_move(input: &str) -> Action {
+        let view = BoardView::read(&mut Scanner::new(input.as_bytes()), Size::Leading).unwrap();
+        Rescuer.next_move(&view)
+    }
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(next_move("5\n2 3\n-----\n-----\np--m-\n-----\n-----\n"), Action::Move(Direction::Left));
+    }
+
+    #[test]
+    fn test_vertical_and_done() {
+        assert_eq!(next_move("3\n0 1\n-m-\n---\n-p-\n"), Action::Move(Direction::Down));
+        assert_eq!(next_move("3\n1 1\n---\n-m-\n---\n"), Action::Stay);
+    }
+
+    #[test]
+    fn test_walls() {
+        assert_eq!(next_move("3\n0 0\nm#p\n-#-\n---\n"), Action::Move(Direction::Down));
+        assert_eq!(next_move("3\n0 0\nm#p\n-#-\n-#-\n"), Action::Stay);
+    }
+}
@@ -1,16 +1,7 @@
-use std::{io, str, fmt};
+extern crate hackerrank;
 
 // https://www.hackerrank.com/challenges/utopian-tree
 
-fn read_line<T>() -> T
-    where T: str::FromStr + fmt::Debug,
-          T::Err: fmt::Debug,
-{
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    input.trim().parse().unwrap()
-}
-
 fn utopian_tree(cycles: i32) -> i32 {
     let mut height = 1;
     for i in 0..cycles {
@@ -25,12 +16,15 @@ fn utopian_tree(cycles: i32) -> i32 {
 
 #[cfg_attr(test, allow(dead_code))]
 fn main() {
-    let count: i32 = read_line();
-    for _ in 0..count {
-        let cycles = read_line();
-        let height = utopian_tree(cycles);
-        println!("{}", height);
-    }
+    hackerrank::run(|scan| {
+        let count: i32 = scan.read()?;
+        for _ in 0..count {
+            let cycles = scan.read()?;
+            let height = utopian_tree(cycles);
+            println!("{}", height);
+        }
+        Ok(())
+    });
 }
 
 #[test]
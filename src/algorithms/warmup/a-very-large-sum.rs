@@ -1,22 +1,12 @@
-use std::io;
-use std::iter::Iterator;
+extern crate hackerrank;
 
-fn read_line() -> String {
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    input
-}
-
-fn read_array() -> Vec<i64> {
-    let mut line = String::new();
-    io::stdin().read_line(&mut line).unwrap();
-    line.trim().split(' ').map(|s| { s.parse().unwrap() }).collect()
-}
+use hackerrank::BigInt;
 
 fn main() {
-    // Consume the count we don't use
-    read_line();
-    let v = read_array();
-    let sum: i64 = v.iter().fold(0, |a, b| { a + b });
-    println!("{}", sum);
+    hackerrank::run(|scan| {
+        let v: Vec<BigInt> = scan.prefixed_vec()?;
+        let sum: BigInt = v.iter().sum();
+        println!("{}", sum);
+        Ok(())
+    });
 }
@@ -1,9 +1,12 @@
-use std::io;
+extern crate hackerrank;
+
 use std::str::FromStr;
 use std::fmt::{Display, Formatter, Error};
 use std::cmp::{PartialOrd, Ord, Ordering};
 
-#[derive(PartialEq, Eq, Ord)]
+use hackerrank::InputError;
+
+#[derive(PartialEq, Eq)]
 struct Date {
     day: i32,
     month: i32,
@@ -12,45 +15,47 @@ struct Date {
 
 impl Date {
     pub fn new(day: i32, month: i32, year: i32) -> Date {
-        Date { day: day, month: month, year: year }
+        Date { day, month, year }
     }
 }
 
 impl FromStr for Date {
-    type Err = ();
+    type Err = InputError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split(" ").collect();
-        assert_eq!(parts.len(), 3);
-        let day: i32 = parts[0].parse().unwrap();
-        let month: i32 = parts[1].parse().unwrap();
-        let year: i32 = parts[2].parse().unwrap();
-        Ok(Date::new(day, month, year))
+        let parts: Vec<&str> = s.split(' ').collect();
+        if parts.len() != 3 {
+            return Err(InputError::new(s, "a date like \"9 6 2015\""));
+        }
+        let mut fields = [0; 3];
+        let mut column = 1;
+        for (field, part) in fields.iter_mut().zip(parts) {
+            *field = part.parse().map_err(|err| {
+                InputError::from(err).or_token::<i32>(part).at(1, column)
+            })?;
+            column += part.len() + 1;
+        }
+        Ok(Date::new(fields[0], fields[1], fields[2]))
     }
 }
 
 impl Display for Date {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        try!(write!(f, "{} {} {}", self.day, self.month, self.year));
-        Ok(())
+        write!(f, "{} {} {}", self.day, self.month, self.year)
     }
 }
 
-impl PartialOrd for Date {
-    fn partial_cmp(&self, other: &Date) -> Option<Ordering> {
+impl Ord for Date {
+    fn cmp(&self, other: &Date) -> Ordering {
         let a = (self.year, self.month, self.day);
         let b = (other.year, other.month, other.day);
-        Some(a.cmp(&b))
+        a.cmp(&b)
     }
 }
 
-fn read_line() -> String {
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    input
-}
-
-fn read_date() -> Date {
-    read_line().trim().parse().unwrap()
+impl PartialOrd for Date {
+    fn partial_cmp(&self, other: &Date) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 fn calculate_fine(turned_in: Date, due: Date) -> i32 {
@@ -67,9 +72,12 @@ fn calculate_fine(turned_in: Date, due: Date) -> i32 {
 
 #[cfg_attr(test, allow(dead_code))]
 fn main() {
-    let turned_in = read_date();
-    let due = read_date();
-    println!("{}", calculate_fine(turned_in, due));
+    hackerrank::run(|scan| {
+        let turned_in: Date = scan.line()?;
+        let due: Date = scan.line()?;
+        println!("{}", calculate_fine(turned_in, due));
+        Ok(())
+    });
 }
 
 #[cfg(test)]
@@ -93,4 +101,13 @@ mod tests {
         let due = Date::new(6, 6, 2015);
         assert_eq!(calculate_fine(turned_in, due), 45);
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = "9 six 2015".parse::<Date>().err().unwrap();
+        assert_eq!(err.token, Some("six".to_string()));
+        assert_eq!(err.column, Some(3));
+
+        assert!("9 6".parse::<Date>().is_err());
+    }
 }
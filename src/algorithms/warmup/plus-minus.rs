@@ -1,62 +1,64 @@
 #![feature(test)]
 extern crate test;
+extern crate hackerrank;
 
-use std::io;
-use std::iter::Iterator;
+use hackerrank::{BigInt, InputError, Rational};
 
-fn read_line() -> String {
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    input
-}
-
-fn read_array() -> Vec<i64> {
-    let line = read_line();
-    line.trim().split(' ').map(|s| { s.parse().unwrap() }).collect()
-}
-
-fn counts(v: Vec<i64>) -> (f64, f64, f64) {
+/// The fractions of `v` that are positive, negative and zero. There are no
+/// fractions of an empty array.
+fn counts(v: Vec<i64>) -> Result<(Rational, Rational, Rational), InputError> {
+    if v.is_empty() {
+        return Err(InputError::new("0", "at least one number"));
+    }
     let mut count_positive = 0;
     let mut count_negative = 0;
     let mut count_zero = 0;
-    let count = v.len();
+    let count = BigInt::from(v.len());
 
     for n in v {
         match n {
             n if n > 0 => count_positive += 1,
             n if n < 0 => count_negative += 1,
-            n if n == 0 => count_zero += 1,
-            _ => unreachable!(),
+            _ => count_zero += 1,
         }
     }
 
-    (
-        count_positive as f64 / count as f64,
-        count_negative as f64 / count as f64,
-        count_zero as f64 / count as f64,
-    )
+    let fraction = |n: u64| Rational::new(BigInt::from(n), count.clone());
+    Ok((fraction(count_positive), fraction(count_negative), fraction(count_zero)))
 }
 
 #[cfg_attr(test, allow(dead_code))]
 fn main() {
-    // consume unused count
-    read_line();
-    let (frac_positive, frac_negative, frac_zero) = counts(read_array());
+    hackerrank::run(|scan| {
+        let n = scan.read()?;
+        let (line, column) = scan.position();
+        let (frac_positive, frac_negative, frac_zero) = counts(scan.vec(n)?)
+            .map_err(|err| err.at(line, column))?;
 
-    println!("{:.3}", frac_positive);
-    println!("{:.3}", frac_negative);
-    println!("{:.3}", frac_zero);
+        println!("{}", frac_positive.to_decimal_string(3));
+        println!("{}", frac_negative.to_decimal_string(3));
+        println!("{}", frac_zero.to_decimal_string(3));
+        Ok(())
+    });
 }
 
 #[cfg(test)]
 mod tests {
     use super::counts;
+    use hackerrank::Rational;
 
     #[test]
     fn sample() {
-        let (pos, neg, zero) = counts(vec![-4, 3, -9, 0, 4, 1]);
-        assert!(pos == 0.5);
-        assert!(neg == 1. / 3.);
-        assert!(zero == 1. / 6.);
+        let (pos, neg, zero) = counts(vec![-4, 3, -9, 0, 4, 1]).unwrap();
+        assert_eq!(pos, "1/2".parse::<Rational>().unwrap());
+        assert_eq!(neg, "1/3".parse::<Rational>().unwrap());
+        assert_eq!(zero, "1/6".parse::<Rational>().unwrap());
+        assert_eq!(zero.to_decimal_string(3), "0.167");
+    }
+
+    #[test]
+    fn empty() {
+        let err = counts(vec![]).unwrap_err();
+        assert_eq!(err.token, Some("0".to_string()));
     }
 }
@@ -1,22 +1,10 @@
-use std::io;
-use std::iter::Iterator;
-
-fn read_line() -> String {
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    input
-}
-
-fn read_array() -> Vec<i32> {
-    let mut line = String::new();
-    io::stdin().read_line(&mut line).unwrap();
-    line.trim().split(' ').map(|s| { s.parse().unwrap() }).collect()
-}
+extern crate hackerrank;
 
 fn main() {
-    // Consume the count we don't use
-    read_line();
-    let v = read_array();
-    let sum: i32 = v.iter().fold(0, |a, b| { a + b });
-    println!("{}", sum);
+    hackerrank::run(|scan| {
+        let v: Vec<i32> = scan.prefixed_vec()?;
+        let sum: i32 = v.iter().sum();
+        println!("{}", sum);
+        Ok(())
+    });
 }
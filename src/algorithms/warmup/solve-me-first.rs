@@ -1,11 +1,10 @@
-use std::io;
-
-fn read_number() -> i32 {
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    input.trim().parse().unwrap()
-}
+extern crate hackerrank;
 
 fn main() {
-    println!("{}", read_number() + read_number());
+    hackerrank::run(|scan| {
+        let a: i32 = scan.read()?;
+        let b: i32 = scan.read()?;
+        println!("{}", a + b);
+        Ok(())
+    });
 }
@@ -1,7 +1,10 @@
-use std::io;
+extern crate hackerrank;
+
 use std::str::FromStr;
 use std::fmt::{Display, Formatter, Error};
 
+use hackerrank::InputError;
+
 #[derive(Clone)]
 pub enum Time {
     AmPm { hour: u8, minute: u8, second: u8, am: bool },
@@ -10,11 +13,11 @@ pub enum Time {
 
 impl Time {
     pub fn new_ampm(hour: u8, minute: u8, second: u8, am: bool) -> Time {
-        Time::AmPm { hour: hour, minute: minute, second: second, am: am }
+        Time::AmPm { hour, minute, second, am }
     }
 
     pub fn new_twenty_four(hour: u8, minute: u8, second: u8) -> Time {
-        Time::TwentyFour { hour: hour, minute: minute, second: second }
+        Time::TwentyFour { hour, minute, second }
     }
 
     pub fn to_twenty_four(&self) -> Time {
@@ -46,13 +49,28 @@ impl Time {
 }
 
 impl FromStr for Time {
-    type Err = ();
+    type Err = InputError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let hour: u8 = s[0..2].parse().unwrap();
-        let minute: u8 = s[3..5].parse().unwrap();
-        let second: u8 = s[6..8].parse().unwrap();
+        if !s.is_ascii() || (s.len() != 8 && s.len() != 10) || &s[2..3] != ":" || &s[5..6] != ":" {
+            return Err(InputError::new(s, "a time like 07:05:45PM"));
+        }
+        let field = |start: usize, name: &str, max: u8| {
+            let digits = &s[start..start + 2];
+            match digits.parse() {
+                Ok(n) if n <= max => Ok(n),
+                _ => Err(InputError::new(digits, format!("{} (00-{})", name, max)).at(1, start + 1)),
+            }
+        };
+        let max_hour = if s.len() > 8 { 12 } else { 23 };
+        let hour = field(0, "hour", max_hour)?;
+        let minute = field(3, "minute", 59)?;
+        let second = field(6, "second", 59)?;
         if s.len() > 8 {
-            let am = s[8..10] == *"AM";
+            let am = match &s[8..10] {
+                "AM" => true,
+                "PM" => false,
+                other => return Err(InputError::new(other, "AM or PM").at(1, 9)),
+            };
             Ok(Time::new_ampm(hour, minute, second, am))
         } else {
             Ok(Time::new_twenty_four(hour, minute, second))
@@ -64,31 +82,24 @@ impl Display for Time {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         match *self {
             Time::AmPm { hour, minute, second, am } => {
-                try!(write!(f, "{:0>2}:{:0>2}:{:0>2}{}",
+                write!(f, "{:0>2}:{:0>2}:{:0>2}{}",
                        hour, minute, second,
-                       if am { "AM" } else { "PM" }));
+                       if am { "AM" } else { "PM" })?;
             },
             Time::TwentyFour { hour, minute, second } => {
-                try!(write!(f, "{:0>2}:{:0>2}:{:0>2}", hour, minute, second));
+                write!(f, "{:0>2}:{:0>2}:{:0>2}", hour, minute, second)?;
             },
         }
         Ok(())
     }
 }
 
-fn read_line() -> String {
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    input
-}
-
-fn read_time() -> Time {
-    read_line().trim().parse().unwrap()
-}
-
 fn main() {
-    let time = read_time();
-    println!("{}", time.to_twenty_four());
+    hackerrank::run(|scan| {
+        let time: Time = scan.read()?;
+        println!("{}", time.to_twenty_four());
+        Ok(())
+    });
 }
 
 #[cfg(test)]
@@ -108,4 +119,16 @@ mod tests {
         let s = format!("{}", t.to_twenty_four());
         assert_eq!(s, "00:40:22");
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = "07:65:45PM".parse::<Time>().err().unwrap();
+        assert_eq!(err.token, Some("65".to_string()));
+        assert_eq!(err.column, Some(4));
+
+        let err = "07:05:45XM".parse::<Time>().err().unwrap();
+        assert_eq!(err.token, Some("XM".to_string()));
+
+        assert!("7:05:45".parse::<Time>().is_err());
+    }
 }
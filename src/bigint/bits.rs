@@ -0,0 +1,239 @@
+//! Shifts, bitwise operations and bit inspection.
+//!
+//! Negative numbers behave as if written in two's complement with infinitely
+//! many leading ones, the way the primitive signed integers do within their
+//! width: `-1 & x == x`, `!x == -x - 1`, and `>>` rounds towards negative
+//! infinity.
+
+use std::cmp::Ordering;
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, ShlAssign, Shr, ShrAssign};
+use std::mem;
+
+use super::{BigInt, limbs};
+
+impl BigInt {
+    /// The number of bits in the magnitude, zero for zero.
+    pub fn bits(&self) -> u64 {
+        limbs::bit_len(&self.limbs)
+    }
+
+    /// The number of trailing zero bits, or `None` for zero. `n` and `-n`
+    /// have the same trailing zeros.
+    pub fn trailing_zeros(&self) -> Option<u64> {
+        limbs::trailing_zeros(&self.limbs)
+    }
+
+    /// The number of one bits in the magnitude. (A negative number has
+    /// infinitely many in two's complement.)
+    pub fn count_ones(&self) -> u64 {
+        self.limbs.iter().map(|limb| limb.count_ones() as u64).sum()
+    }
+
+    /// Whether bit `i` of the two's-complement representation is set.
+    pub fn test_bit(&self, i: u64) -> bool {
+        if !self.negative {
+            return limbs::test_bit(&self.limbs, i);
+        }
+        // -m is !(m - 1): the bits of m up to its lowest one are unchanged,
+        // and every bit above that is flipped.
+        match i.cmp(&self.trailing_zeros().unwrap()) {
+            Ordering::Less => false,
+            Ordering::Equal => true,
+            Ordering::Greater => !limbs::test_bit(&self.limbs, i),
+        }
+    }
+
+    /// The low `len` limbs of the two's-complement representation. `len`
+    /// must leave room for a sign bit.
+    fn to_twos_complement(&self, len: usize) -> Vec<u32> {
+        if !self.negative {
+            let mut v = self.limbs.clone();
+            v.resize(len, 0);
+            return v;
+        }
+        let mut v = limbs::sub(&self.limbs, &[1]);
+        v.resize(len, 0);
+        for limb in &mut v {
+            *limb = !*limb;
+        }
+        v
+    }
+
+    fn from_twos_complement(mut v: Vec<u32>) -> BigInt {
+        let negative = v.last().is_some_and(|&top| top >> 31 == 1);
+        if !negative {
+            return BigInt::from_parts(false, v);
+        }
+        for limb in &mut v {
+            *limb = !*limb;
+        }
+        BigInt::from_parts(true, limbs::add(&v, &[1]))
+    }
+
+    fn bitwise<F>(&self, rhs: &BigInt, op: F) -> BigInt
+        where F: Fn(u32, u32) -> u32
+    {
+        let len = self.limbs.len().max(rhs.limbs.len()) + 1;
+        let a = self.to_twos_complement(len);
+        let b = rhs.to_twos_complement(len);
+        BigInt::from_twos_complement(a.iter().zip(&b).map(|(&x, &y)| op(x, y)).collect())
+    }
+}
+
+impl BitAnd<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn bitand(self, rhs: &BigInt) -> BigInt {
+        self.bitwise(rhs, |x, y| x & y)
+    }
+}
+
+impl BitOr<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn bitor(self, rhs: &BigInt) -> BigInt {
+        self.bitwise(rhs, |x, y| x | y)
+    }
+}
+
+impl BitXor<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn bitxor(self, rhs: &BigInt) -> BigInt {
+        self.bitwise(rhs, |x, y| x ^ y)
+    }
+}
+
+impl Not for BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> BigInt {
+        -(self + BigInt::one())
+    }
+}
+
+impl Not for &BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> BigInt {
+        -(self + &BigInt::one())
+    }
+}
+
+impl Shl<u64> for &BigInt {
+    type Output = BigInt;
+
+    fn shl(self, bits: u64) -> BigInt {
+        BigInt::from_parts(self.negative, limbs::shl(&self.limbs, bits))
+    }
+}
+
+impl Shr<u64> for &BigInt {
+    type Output = BigInt;
+
+    fn shr(self, bits: u64) -> BigInt {
+        let magnitude = limbs::shr(&self.limbs, bits);
+        // Round towards negative infinity: if a negative number loses any
+        // one bits, its magnitude rounds up.
+        if self.negative && self.trailing_zeros().unwrap() < bits {
+            BigInt::from_parts(true, limbs::add(&magnitude, &[1]))
+        } else {
+            BigInt::from_parts(self.negative, magnitude)
+        }
+    }
+}
+
+impl Shl<u64> for BigInt {
+    type Output = BigInt;
+
+    fn shl(self, bits: u64) -> BigInt {
+        &self << bits
+    }
+}
+
+impl Shr<u64> for BigInt {
+    type Output = BigInt;
+
+    fn shr(self, bits: u64) -> BigInt {
+        &self >> bits
+    }
+}
+
+impl ShlAssign<u64> for BigInt {
+    fn shl_assign(&mut self, bits: u64) {
+        *self = mem::take(self) << bits;
+    }
+}
+
+impl ShrAssign<u64> for BigInt {
+    fn shr_assign(&mut self, bits: u64) {
+        *self = mem::take(self) >> bits;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BigInt;
+    use test_support::next_random_u128;
+
+    /// Pseudo-random i128s of assorted sizes and signs.
+    fn samples() -> Vec<i128> {
+        let mut seed = 1;
+        let mut v = vec![0, 1, -1, i128::MAX >> 1, i128::MIN >> 1];
+        for i in 0..300 {
+            v.push((next_random_u128(&mut seed) as i128) >> (2 + i % 120));
+        }
+        v
+    }
+
+    #[test]
+    fn test_bitwise_matches_i128() {
+        let samples = samples();
+        for pair in samples.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let (x, y) = (BigInt::from(a), BigInt::from(b));
+            assert_eq!(&x & &y, BigInt::from(a & b), "{} & {}", a, b);
+            assert_eq!(&x | &y, BigInt::from(a | b), "{} | {}", a, b);
+            assert_eq!(x.clone() ^ y, BigInt::from(a ^ b), "{} ^ {}", a, b);
+            assert_eq!(!&x, BigInt::from(!a));
+        }
+    }
+
+    #[test]
+    fn test_shifts_match_i128() {
+        for &a in &samples() {
+            let x = BigInt::from(a);
+            for shift in [0, 1, 31, 32, 33, 64, 100, 200] {
+                assert_eq!(&x >> shift, BigInt::from(a >> shift.min(127)), "{} >> {}", a, shift);
+                if a.unsigned_abs().leading_zeros() as u64 > shift + 1 {
+                    assert_eq!(&x << shift, BigInt::from(a << shift), "{} << {}", a, shift);
+                }
+                assert_eq!((&x << shift) >> shift, x);
+            }
+        }
+
+        let mut n = BigInt::from(-5);
+        n <<= 100;
+        n >>= 101;
+        assert_eq!(n, BigInt::from(-3));
+    }
+
+    #[test]
+    fn test_bit_inspection() {
+        for &a in &samples() {
+            let x = BigInt::from(a);
+            for i in 0..140 {
+                assert_eq!(x.test_bit(i), (a >> i.min(127)) & 1 == 1, "bit {} of {}", i, a);
+            }
+            let m = a.unsigned_abs();
+            assert_eq!(x.bits(), 128 - m.leading_zeros() as u64);
+            assert_eq!(x.count_ones(), m.count_ones() as u64);
+            let tz = if a == 0 { None } else { Some(a.trailing_zeros() as u64) };
+            assert_eq!(x.trailing_zeros(), tz);
+        }
+
+        let big = BigInt::one() << 1000;
+        assert_eq!((big.bits(), big.trailing_zeros(), big.count_ones()), (1001, Some(1000), 1));
+        assert!((-&big).test_bit(5000));
+    }
+}
@@ -0,0 +1,375 @@
+//! Arithmetic on magnitudes: little-endian slices of base 2^32 limbs.
+//!
+//! Inputs need not be normalized, but every returned `Vec` is.
+
+use std::cmp::Ordering;
+
+/// Drop high zero limbs.
+pub fn normalize(limbs: &mut Vec<u32>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+/// The length of `limbs` without any high zero limbs.
+fn significant_len(limbs: &[u32]) -> usize {
+    limbs.iter().rposition(|&l| l != 0).map_or(0, |i| i + 1)
+}
+
+pub fn cmp(a: &[u32], b: &[u32]) -> Ordering {
+    let a = &a[..significant_len(a)];
+    let b = &b[..significant_len(b)];
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+pub fn add(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &limb) in long.iter().enumerate() {
+        let t = limb as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push(t as u32);
+        carry = t >> 32;
+    }
+    sum.push(carry as u32);
+    normalize(&mut sum);
+    sum
+}
+
+/// `a - b`. Panics unless `a >= b`.
+pub fn sub(a: &[u32], b: &[u32]) -> Vec<u32> {
+    assert!(cmp(a, b) != Ordering::Less, "magnitude underflow");
+    let mut diff = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &limb) in a.iter().enumerate() {
+        let t = limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        diff.push(t as u32);
+        borrow = if t < 0 { 1 } else { 0 };
+    }
+    normalize(&mut diff);
+    diff
+}
+
+/// Operands shorter than this many limbs are multiplied with the schoolbook
+/// method; Karatsuba's extra additions only pay off above it.
+pub const KARATSUBA_THRESHOLD: usize = 48;
+
+pub fn mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    mul_with_threshold(a, b, KARATSUBA_THRESHOLD)
+}
+
+/// `a * b`, switching from Karatsuba to schoolbook multiplication once the
+/// shorter operand has fewer than `threshold` limbs.
+pub fn mul_with_threshold(a: &[u32], b: &[u32], threshold: usize) -> Vec<u32> {
+    let a = &a[..significant_len(a)];
+    let b = &b[..significant_len(b)];
+    let mut product = vec![0u32; a.len() + b.len()];
+    mul_into(&mut product, a, b, threshold);
+    normalize(&mut product);
+    product
+}
+
+/// `out += a * b`, where `out` is long enough to hold the sum.
+fn mul_into(out: &mut [u32], a: &[u32], b: &[u32], threshold: usize) {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if b.is_empty() {
+        return;
+    }
+    if b.len() < threshold.max(2) {
+        schoolbook_into(out, a, b);
+        return;
+    }
+    if a.len() >= 2 * b.len() {
+        // Very unbalanced: multiply `b` by `a` one `b`-sized chunk at a time,
+        // so each product is balanced.
+        for (i, chunk) in a.chunks(b.len()).enumerate() {
+            mul_into(&mut out[i * b.len()..], chunk, b, threshold);
+        }
+        return;
+    }
+
+    // With a = a1·B^m + a0 and b = b1·B^m + b0:
+    // a·b = z2·B^2m + ((a0 + a1)(b0 + b1) - z2 - z0)·B^m + z0
+    let m = a.len().div_ceil(2);
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m.min(b.len()));
+    let z0 = mul_with_threshold(a0, b0, threshold);
+    let z2 = mul_with_threshold(a1, b1, threshold);
+    let z1 = mul_with_threshold(&add(a0, a1), &add(b0, b1), threshold);
+    let z1 = sub(&sub(&z1, &z0), &z2);
+
+    add_into(out, &z0);
+    add_into(&mut out[m..], &z1);
+    add_into(&mut out[2 * m..], &z2);
+}
+
+/// `out += a * b` by long multiplication, without allocating.
+fn schoolbook_into(out: &mut [u32], a: &[u32], b: &[u32]) {
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u64 * y as u64 + out[i + j] as u64 + carry;
+            out[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        add_into(&mut out[i + b.len()..], &[carry as u32]);
+    }
+}
+
+/// `out += x`, propagating the carry as far as needed. Panics if the sum
+/// doesn't fit in `out`.
+fn add_into(out: &mut [u32], x: &[u32]) {
+    let mut carry = 0u64;
+    let mut i = 0;
+    while i < x.len() || carry > 0 {
+        let t = out[i] as u64 + *x.get(i).unwrap_or(&0) as u64 + carry;
+        out[i] = t as u32;
+        carry = t >> 32;
+        i += 1;
+    }
+}
+
+/// `limbs = limbs * m + a`, in place.
+pub fn mul_add_small(limbs: &mut Vec<u32>, m: u32, a: u32) {
+    let mut carry = a as u64;
+    for limb in limbs.iter_mut() {
+        let t = *limb as u64 * m as u64 + carry;
+        *limb = t as u32;
+        carry = t >> 32;
+    }
+    limbs.push(carry as u32);
+    normalize(limbs);
+}
+
+/// `limbs = limbs / d`, in place, returning the remainder. Inlined so that
+/// division by a constant compiles to a multiplication.
+#[inline(always)]
+pub fn div_rem_small(limbs: &mut Vec<u32>, d: u32) -> u32 {
+    assert!(d != 0, "division by zero");
+    let mut rem = 0u64;
+    for limb in limbs.iter_mut().rev() {
+        let t = (rem << 32) | *limb as u64;
+        *limb = (t / d as u64) as u32;
+        rem = t % d as u64;
+    }
+    normalize(limbs);
+    rem as u32
+}
+
+/// The number of significant bits.
+pub fn bit_len(limbs: &[u32]) -> u64 {
+    let len = significant_len(limbs);
+    if len == 0 {
+        0
+    } else {
+        32 * len as u64 - limbs[len - 1].leading_zeros() as u64
+    }
+}
+
+/// Whether bit `i` (counting from the least significant) is set.
+pub fn test_bit(limbs: &[u32], i: u64) -> bool {
+    match limbs.get((i / 32) as usize) {
+        Some(&limb) => limb >> (i % 32) & 1 == 1,
+        None => false,
+    }
+}
+
+/// `limbs << bits`.
+pub fn shl(limbs: &[u32], bits: u64) -> Vec<u32> {
+    let mut shifted = vec![0; (bits / 32) as usize];
+    shifted.extend(shl_small(limbs, (bits % 32) as u32));
+    normalize(&mut shifted);
+    shifted
+}
+
+/// `limbs >> bits`, discarding the bits shifted out.
+pub fn shr(limbs: &[u32], bits: u64) -> Vec<u32> {
+    match limbs.get((bits / 32) as usize..) {
+        Some(high) => shr_small(high, (bits % 32) as u32),
+        None => vec![],
+    }
+}
+
+/// The number of trailing zero bits, or `None` if the value is zero.
+pub fn trailing_zeros(limbs: &[u32]) -> Option<u64> {
+    let i = limbs.iter().position(|&limb| limb != 0)?;
+    Some(32 * i as u64 + limbs[i].trailing_zeros() as u64)
+}
+
+/// `limbs << shift` for `shift < 32`, always one limb longer than the input.
+fn shl_small(limbs: &[u32], shift: u32) -> Vec<u32> {
+    let mut shifted = Vec::with_capacity(limbs.len() + 1);
+    let mut carry = 0;
+    for &limb in limbs {
+        shifted.push(limb << shift | carry);
+        carry = if shift == 0 { 0 } else { limb >> (32 - shift) };
+    }
+    shifted.push(carry);
+    shifted
+}
+
+/// `limbs >> shift` for `shift < 32`.
+fn shr_small(limbs: &[u32], shift: u32) -> Vec<u32> {
+    let mut shifted: Vec<u32> = limbs.iter().enumerate().map(|(i, &limb)| {
+        let high = if shift == 0 { 0 } else { *limbs.get(i + 1).unwrap_or(&0) << (32 - shift) };
+        limb >> shift | high
+    }).collect();
+    normalize(&mut shifted);
+    shifted
+}
+
+/// Quotient and remainder of `a / b`, using Knuth's Algorithm D (TAOCP
+/// vol. 2, 4.3.1). Panics if `b` is zero.
+pub fn div_rem(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let a = &a[..significant_len(a)];
+    let b = &b[..significant_len(b)];
+    assert!(!b.is_empty(), "division by zero");
+    if cmp(a, b) == Ordering::Less {
+        return (vec![], a.to_vec());
+    }
+    if b.len() == 1 {
+        let mut q = a.to_vec();
+        let mut r = vec![div_rem_small(&mut q, b[0])];
+        normalize(&mut r);
+        return (q, r);
+    }
+
+    // Scale both operands so the divisor's top limb has its high bit set,
+    // which keeps each quotient-digit estimate within 2 of the truth.
+    let shift = b[b.len() - 1].leading_zeros();
+    let mut v = shl_small(b, shift);
+    v.pop();
+    let mut u = shl_small(a, shift);
+    let n = v.len();
+    let m = a.len() - n;
+    let v_top = v[n - 1] as u64;
+    let v_next = v[n - 2] as u64;
+    let mut q = vec![0u32; m + 1];
+
+    for j in (0..m + 1).rev() {
+        let top = (u[j + n] as u64) << 32 | u[j + n - 1] as u64;
+        let mut q_hat = top / v_top;
+        let mut r_hat = top % v_top;
+        while q_hat >> 32 != 0 || q_hat * v_next > (r_hat << 32 | u[j + n - 2] as u64) {
+            q_hat -= 1;
+            r_hat += v_top;
+            if r_hat >> 32 != 0 {
+                break;
+            }
+        }
+
+        // u[j..j + n + 1] -= q_hat * v
+        let mut borrow = 0i64;
+        let mut carry = 0u64;
+        for i in 0..n {
+            let p = q_hat * v[i] as u64 + carry;
+            carry = p >> 32;
+            let t = u[i + j] as i64 - borrow - (p & 0xffff_ffff) as i64;
+            u[i + j] = t as u32;
+            borrow = if t < 0 { 1 } else { 0 };
+        }
+        let t = u[j + n] as i64 - borrow - carry as i64;
+        u[j + n] = t as u32;
+
+        // The estimate was one too large: add the divisor back.
+        if t < 0 {
+            q_hat -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let t = u[i + j] as u64 + v[i] as u64 + carry;
+                u[i + j] = t as u32;
+                carry = t >> 32;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u32);
+        }
+        q[j] = q_hat as u32;
+    }
+
+    normalize(&mut q);
+    (q, shr_small(&u[..n], shift))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{add, bit_len, cmp, div_rem, mul_with_threshold, shl, shr, sub, trailing_zeros};
+    use test_support::next_random;
+
+    #[test]
+    fn test_cmp_ignores_high_zeros() {
+        assert_eq!(cmp(&[1, 0, 0], &[1]), Ordering::Equal);
+        assert_eq!(cmp(&[0, 1], &[5]), Ordering::Greater);
+        assert_eq!(cmp(&[5, 1], &[0, 2]), Ordering::Less);
+        assert_eq!(cmp(&[], &[0]), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_add_sub_borrow_chain() {
+        let a = [0, 0, 1];
+        let b = [1];
+        let diff = sub(&a, &b);
+        assert_eq!(diff, vec![u32::MAX, u32::MAX]);
+        assert_eq!(add(&diff, &b), a.to_vec());
+        assert_eq!(sub(&a, &a), vec![]);
+    }
+
+    #[test]
+    fn test_div_rem_add_back() {
+        // A case where the first quotient-digit estimate is too large even
+        // after the two-limb correction, so the divisor must be added back.
+        let a = [0, 0, 0x8000_0000, 0x7fff_ffff];
+        let b = [1, 0, 0x8000_0000];
+        assert_eq!(div_rem(&a, &b), (vec![0xffff_fffe], vec![2, 0xffff_ffff, 0x7fff_ffff]));
+    }
+
+    #[test]
+    fn test_karatsuba_matches_schoolbook() {
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        let mut limbs = |len: usize| -> Vec<u32> {
+            (0..len).map(|_| {
+                let r = next_random(&mut seed) >> 32;
+                // Mix in runs of all-ones limbs to stress the carries.
+                if r.is_multiple_of(5) { u32::MAX } else { r as u32 }
+            }).collect()
+        };
+        for &(a_len, b_len) in &[(1, 1), (2, 2), (3, 2), (7, 7), (8, 3), (20, 19),
+                                 (33, 17), (64, 64), (100, 7), (129, 65)] {
+            let a = limbs(a_len);
+            let b = limbs(b_len);
+            let expected = mul_with_threshold(&a, &b, usize::MAX);
+            for &threshold in &[2, 3, 8, 32] {
+                assert_eq!(mul_with_threshold(&a, &b, threshold), expected,
+                           "{}x{} limbs, threshold {}", a_len, b_len, threshold);
+                assert_eq!(mul_with_threshold(&b, &a, threshold), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bit_len() {
+        assert_eq!(bit_len(&[]), 0);
+        assert_eq!(bit_len(&[1]), 1);
+        assert_eq!(bit_len(&[0, 1, 0]), 33);
+        assert_eq!(bit_len(&[u32::MAX]), 32);
+    }
+
+    #[test]
+    fn test_shifts() {
+        assert_eq!(shl(&[0x8000_0001], 1), vec![2, 1]);
+        assert_eq!(shl(&[1], 64), vec![0, 0, 1]);
+        assert_eq!(shl(&[], 100), vec![]);
+        assert_eq!(shr(&[0, 0, 1], 64), vec![1]);
+        assert_eq!(shr(&[2, 1], 1), vec![0x8000_0001]);
+        assert_eq!(shr(&[1], 1), vec![]);
+        assert_eq!(shr(&[1], 1000), vec![]);
+        assert_eq!(trailing_zeros(&[0, 8]), Some(35));
+        assert_eq!(trailing_zeros(&[]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "magnitude underflow")]
+    fn test_sub_underflow() {
+        sub(&[1], &[2]);
+    }
+}
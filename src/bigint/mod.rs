@@ -0,0 +1,812 @@
+//! Arbitrary-precision integers.
+
+use std::{error, mem};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter, Error};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+use std::ops::{Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+use error::InputError;
+
+mod bits;
+mod limbs;
+mod radix;
+
+/// An arbitrary-precision signed integer.
+///
+/// Stored in sign-magnitude form. The magnitude is little-endian base 2^32
+/// limbs with no high zero limbs, and zero is never negative, so equal values
+/// always have equal representations.
+#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+pub struct BigInt {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    /// Build a value from a sign and a possibly unnormalized magnitude.
+    fn from_parts(negative: bool, mut limbs: Vec<u32>) -> BigInt {
+        limbs::normalize(&mut limbs);
+        let negative = negative && !limbs.is_empty();
+        BigInt { negative, limbs }
+    }
+
+    pub fn from_u64(n: u64) -> BigInt {
+        BigInt::from_parts(false, vec![n as u32, (n >> 32) as u32])
+    }
+
+    pub fn from_i64(n: i64) -> BigInt {
+        let magnitude = n.unsigned_abs();
+        BigInt::from_parts(n < 0, vec![magnitude as u32, (magnitude >> 32) as u32])
+    }
+
+    fn from_u128(n: u128) -> BigInt {
+        BigInt::from_parts(false, vec![n as u32, (n >> 32) as u32, (n >> 64) as u32, (n >> 96) as u32])
+    }
+
+    fn from_i128(n: i128) -> BigInt {
+        let magnitude = BigInt::from_u128(n.unsigned_abs());
+        BigInt::from_parts(n < 0, magnitude.limbs)
+    }
+
+    /// The magnitude as a `u128`, if it fits.
+    fn magnitude_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        Some(self.limbs.iter().rev().fold(0, |acc, &limb| acc << 32 | limb as u128))
+    }
+
+    pub fn zero() -> BigInt {
+        BigInt { negative: false, limbs: vec![] }
+    }
+
+    pub fn one() -> BigInt {
+        BigInt { negative: false, limbs: vec![1] }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_positive(&self) -> bool {
+        !self.negative && !self.is_zero()
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt { negative: false, limbs: self.limbs.clone() }
+    }
+
+    /// -1, 0 or 1 according to the sign of `self`.
+    pub fn signum(&self) -> BigInt {
+        if self.is_zero() {
+            BigInt::zero()
+        } else {
+            BigInt { negative: self.negative, limbs: vec![1] }
+        }
+    }
+}
+
+impl BigInt {
+    /// Quotient and remainder, truncating towards zero like the primitive
+    /// integer types: the remainder has the sign of `self`. Panics if
+    /// `divisor` is zero.
+    pub fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        let (q, r) = limbs::div_rem(&self.limbs, &divisor.limbs);
+        (BigInt::from_parts(self.negative != divisor.negative, q),
+         BigInt::from_parts(self.negative, r))
+    }
+
+    pub fn pow(&self, mut exp: u32) -> BigInt {
+        let mut base = self.clone();
+        let mut acc = BigInt::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc *= &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        acc
+    }
+
+    /// `self^exp mod modulus`, in the range `0..modulus`. Panics if `exp` is
+    /// negative or `modulus` is not positive.
+    pub fn modpow(&self, exp: &BigInt, modulus: &BigInt) -> BigInt {
+        assert!(!exp.is_negative(), "negative exponent");
+        assert!(modulus.is_positive(), "modulus must be positive");
+        let reduce = |n: BigInt| {
+            let r = n % modulus;
+            if r.is_negative() { r + modulus } else { r }
+        };
+
+        let base = reduce(self.clone());
+        let mut acc = reduce(BigInt::one());
+        for i in (0..exp.bits()).rev() {
+            acc = reduce(&acc * &acc);
+            if exp.test_bit(i) {
+                acc = reduce(acc * &base);
+            }
+        }
+        acc
+    }
+
+    /// The greatest common divisor, always non-negative. `gcd(0, 0)` is 0.
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        let mut a = self.abs();
+        let mut b = other.abs();
+        while !b.is_zero() {
+            let r = &a % &b;
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    /// The largest integer whose square is at most `self`. Panics if `self`
+    /// is negative.
+    pub fn isqrt(&self) -> BigInt {
+        assert!(!self.is_negative(), "square root of a negative number");
+        if self.is_zero() {
+            return BigInt::zero();
+        }
+        // Newton's method from a power of two that is at least the root
+        // decreases monotonically until it reaches it.
+        let mut x = BigInt::one() << self.bits().div_ceil(2);
+        loop {
+            let y = (&x + self / &x) >> 1;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+}
+
+impl Default for BigInt {
+    fn default() -> BigInt {
+        BigInt::zero()
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = InputError;
+
+    /// Decimal digits with an optional leading `+` or `-`.
+    fn from_str(s: &str) -> Result<BigInt, InputError> {
+        BigInt::from_str_radix(s, 10)
+    }
+}
+
+/// The error from converting a `BigInt` into a primitive integer too small
+/// to hold it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromBigIntError(());
+
+impl Display for TryFromBigIntError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("out of range integral type conversion attempted")
+    }
+}
+
+impl error::Error for TryFromBigIntError {}
+
+macro_rules! impl_unsigned_conversions {
+    ($($t:ty)*) => {$(
+        impl From<$t> for BigInt {
+            fn from(n: $t) -> BigInt {
+                BigInt::from_u128(n as u128)
+            }
+        }
+
+        impl TryFrom<&BigInt> for $t {
+            type Error = TryFromBigIntError;
+
+            fn try_from(n: &BigInt) -> Result<$t, TryFromBigIntError> {
+                match n.magnitude_u128() {
+                    Some(m) if !n.negative => <$t>::try_from(m).map_err(|_| TryFromBigIntError(())),
+                    _ => Err(TryFromBigIntError(())),
+                }
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed_conversions {
+    ($($t:ty)*) => {$(
+        impl From<$t> for BigInt {
+            fn from(n: $t) -> BigInt {
+                BigInt::from_i128(n as i128)
+            }
+        }
+
+        impl TryFrom<&BigInt> for $t {
+            type Error = TryFromBigIntError;
+
+            fn try_from(n: &BigInt) -> Result<$t, TryFromBigIntError> {
+                let m = n.magnitude_u128().ok_or(TryFromBigIntError(()))?;
+                let value = if n.negative {
+                    // i128::MIN has no positive counterpart, so go via the
+                    // wrapped negation.
+                    if m > 1 << 127 {
+                        return Err(TryFromBigIntError(()));
+                    }
+                    (m as i128).wrapping_neg()
+                } else {
+                    i128::try_from(m).map_err(|_| TryFromBigIntError(()))?
+                };
+                <$t>::try_from(value).map_err(|_| TryFromBigIntError(()))
+            }
+        }
+    )*};
+}
+
+impl_unsigned_conversions!(u8 u16 u32 u64 u128 usize);
+impl_signed_conversions!(i8 i16 i32 i64 i128 isize);
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => limbs::cmp(&self.limbs, &other.limbs),
+            (true, true) => limbs::cmp(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.limbs)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.limbs.clone())
+    }
+}
+
+impl BigInt {
+    /// `self + (-1)^rhs_negative · rhs`, shared by addition and subtraction.
+    fn add_signed(&self, rhs_negative: bool, rhs: &[u32]) -> BigInt {
+        if self.negative == rhs_negative {
+            return BigInt::from_parts(self.negative, limbs::add(&self.limbs, rhs));
+        }
+        // Opposite signs: the result takes the sign of the larger magnitude.
+        match limbs::cmp(&self.limbs, rhs) {
+            Ordering::Equal => BigInt::zero(),
+            Ordering::Greater => BigInt::from_parts(self.negative, limbs::sub(&self.limbs, rhs)),
+            Ordering::Less => BigInt::from_parts(rhs_negative, limbs::sub(rhs, &self.limbs)),
+        }
+    }
+}
+
+impl Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: &BigInt) -> BigInt {
+        self.add_signed(rhs.negative, &rhs.limbs)
+    }
+}
+
+impl Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: &BigInt) -> BigInt {
+        self.add_signed(!rhs.negative, &rhs.limbs)
+    }
+}
+
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: &BigInt) -> BigInt {
+        BigInt::from_parts(self.negative != rhs.negative, limbs::mul(&self.limbs, &rhs.limbs))
+    }
+}
+
+impl Div<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, rhs: &BigInt) -> BigInt {
+        self.div_rem(rhs).0
+    }
+}
+
+impl Rem<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, rhs: &BigInt) -> BigInt {
+        self.div_rem(rhs).1
+    }
+}
+
+forward_binop!(BigInt: Add, add, AddAssign, add_assign);
+forward_binop!(BigInt: Sub, sub, SubAssign, sub_assign);
+forward_binop!(BigInt: Mul, mul, MulAssign, mul_assign);
+forward_binop!(BigInt: Div, div, DivAssign, div_assign);
+forward_binop!(BigInt: Rem, rem, RemAssign, rem_assign);
+forward_binop!(BigInt: BitAnd, bitand, BitAndAssign, bitand_assign);
+forward_binop!(BigInt: BitOr, bitor, BitOrAssign, bitor_assign);
+forward_binop!(BigInt: BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl Mul<u64> for BigInt {
+    type Output = BigInt;
+
+    fn mul(mut self, rhs: u64) -> BigInt {
+        if rhs <= u32::MAX as u64 {
+            limbs::mul_add_small(&mut self.limbs, rhs as u32, 0);
+            BigInt::from_parts(self.negative, self.limbs)
+        } else {
+            self * BigInt::from_u64(rhs)
+        }
+    }
+}
+
+impl Mul<u64> for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: u64) -> BigInt {
+        self.clone() * rhs
+    }
+}
+
+impl MulAssign<u64> for BigInt {
+    fn mul_assign(&mut self, rhs: u64) {
+        *self = mem::take(self) * rhs;
+    }
+}
+
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = BigInt>>(iter: I) -> BigInt {
+        iter.fold(BigInt::zero(), |acc, n| acc + n)
+    }
+}
+
+impl<'a> Sum<&'a BigInt> for BigInt {
+    fn sum<I: Iterator<Item = &'a BigInt>>(iter: I) -> BigInt {
+        iter.fold(BigInt::zero(), |acc, n| acc + n)
+    }
+}
+
+impl Product for BigInt {
+    fn product<I: Iterator<Item = BigInt>>(iter: I) -> BigInt {
+        iter.fold(BigInt::one(), |acc, n| acc * n)
+    }
+}
+
+impl<'a> Product<&'a BigInt> for BigInt {
+    fn product<I: Iterator<Item = &'a BigInt>>(iter: I) -> BigInt {
+        iter.fold(BigInt::one(), |acc, n| acc * n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::str::FromStr;
+
+    use scanner::Scanner;
+    use super::{BigInt, limbs};
+    use test::Bencher;
+    use test_support::next_random;
+
+    #[test]
+    fn test_big_int_add() {
+        let a = BigInt::from_str("123").unwrap();
+        let b = BigInt::from_str("456").unwrap();
+        let expected = BigInt::from_str("579").unwrap();
+        let actual = a + b;
+        assert_eq!(expected, actual);
+    }
+
+    #[bench]
+    fn test_big_int_add_bigger(bench: &mut Bencher) {
+        bench.iter(|| {
+            let a = BigInt::from_str("36893488147419103232").unwrap();
+            let b = BigInt::from_str("295147905179352825856").unwrap();
+            let expected = BigInt::from_str("332041393326771929088").unwrap();
+            let actual = a + b;
+            assert_eq!(actual, expected);
+            actual
+        });
+    }
+
+    #[test]
+    fn test_big_int_add_carry_with_unequal_lengths() {
+        let a = BigInt::from_str("1368").unwrap();
+        let b = BigInt::from_str("9120").unwrap();
+        let c = BigInt::from_str("45600").unwrap();
+        let expected = BigInt::from_str("56088").unwrap();
+        let actual = a + b + c;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_big_int_add_carry_across_limbs() {
+        let a = BigInt::from_u64(u32::MAX as u64);
+        let b = BigInt::from_u64(u64::MAX);
+        assert_eq!(a + b, BigInt::from_str("18446744078004518910").unwrap());
+    }
+
+    #[test]
+    fn test_bit_int_mul() {
+        let a = BigInt::from_str("123").unwrap();
+        let b = BigInt::from_str("456").unwrap();
+        let expected = BigInt::from_str("56088").unwrap();
+        let actual = a * b;
+        assert_eq!(actual, expected);
+    }
+
+    #[bench]
+    fn test_big_int_mult_bigger(bench: &mut Bencher) {
+        bench.iter(|| {
+            let a = BigInt::from_str("36893488147419103232").unwrap();
+            let b = BigInt::from_str("295147905179352825856").unwrap();
+            let expected = BigInt::from_str("10889035741470030830827987437816582766592").unwrap();
+            let actual = a * b;
+            assert_eq!(actual, expected);
+            actual
+        });
+    }
+
+    /// Two 10,000-digit operands.
+    fn ten_thousand_digits() -> (BigInt, BigInt) {
+        let a = BigInt::from_u64(7).pow(11832) + BigInt::from_u64(12345);
+        let b = BigInt::from_u64(3).pow(20957) - BigInt::from_u64(67890);
+        (a, b)
+    }
+
+    #[test]
+    fn test_mul_ten_thousand_digits() {
+        let (a, b) = ten_thousand_digits();
+        assert_eq!(format!("{}", a).len(), 10000);
+        assert_eq!(format!("{}", b).len(), 10000);
+        let expected = BigInt::from_parts(false, limbs::mul_with_threshold(&a.limbs, &b.limbs, usize::MAX));
+        assert_eq!(a * b, expected);
+    }
+
+    #[bench]
+    fn bench_mul_ten_thousand_digits_schoolbook(bench: &mut Bencher) {
+        let (a, b) = ten_thousand_digits();
+        bench.iter(|| limbs::mul_with_threshold(&a.limbs, &b.limbs, usize::MAX));
+    }
+
+    #[bench]
+    fn bench_mul_ten_thousand_digits_karatsuba(bench: &mut Bencher) {
+        let (a, b) = ten_thousand_digits();
+        bench.iter(|| limbs::mul(&a.limbs, &b.limbs));
+    }
+
+    #[test]
+    fn test_mul_u64() {
+        let a = BigInt::from_str("340282366920938463463374607431768211456").unwrap();
+        assert_eq!(a.clone() * 10, BigInt::from_str("3402823669209384634633746074317682114560").unwrap());
+        assert_eq!(a * u64::MAX,
+                   BigInt::from_str("6277101735386680763495507056286727952638980837032266301440").unwrap());
+    }
+
+    #[test]
+    fn test_zero_is_canonical() {
+        assert_eq!(BigInt::from_u64(0), BigInt::zero());
+        assert_eq!(BigInt::from_str("0000").unwrap(), BigInt::zero());
+        assert_eq!(BigInt::from_str("123").unwrap() * BigInt::zero(), BigInt::zero());
+        assert_eq!(BigInt::from_str("00123").unwrap(), BigInt::from_u64(123));
+    }
+
+    #[test]
+    fn test_sub() {
+        let a = BigInt::from_str("10000000000000000000000").unwrap();
+        let b = BigInt::from_str("1").unwrap();
+        assert_eq!(a.clone() - b.clone(), BigInt::from_str("9999999999999999999999").unwrap());
+        assert_eq!(b.clone() - a.clone(), BigInt::from_str("-9999999999999999999999").unwrap());
+        assert_eq!(a.clone() - a.clone(), BigInt::zero());
+        assert_eq!(BigInt::from_i64(-5) - BigInt::from_i64(-7), BigInt::from_u64(2));
+    }
+
+    #[test]
+    fn test_signed_add_mul() {
+        let a = BigInt::from_i64(-123);
+        let b = BigInt::from_i64(456);
+        assert_eq!(a.clone() + b.clone(), BigInt::from_u64(333));
+        assert_eq!(a.clone() * b.clone(), BigInt::from_i64(-56088));
+        assert_eq!(a.clone() * a.clone(), BigInt::from_u64(15129));
+        assert_eq!(a.clone() * 2, BigInt::from_i64(-246));
+        let zero: u64 = 0;
+        assert_eq!(a * zero, BigInt::zero());
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(-BigInt::from_u64(5), BigInt::from_i64(-5));
+        assert_eq!(-BigInt::from_i64(-5), BigInt::from_u64(5));
+        assert_eq!(-BigInt::zero(), BigInt::zero());
+        assert!(!(-BigInt::zero()).is_negative());
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut values: Vec<BigInt> = ["5", "-100000000000000000000", "0", "-3",
+                                       "100000000000000000000", "4294967296", "-4294967296"]
+            .iter().map(|s| BigInt::from_str(s).unwrap()).collect();
+        values.sort();
+        let sorted: Vec<String> = values.iter().map(|n| format!("{}", n)).collect();
+        assert_eq!(sorted, vec!["-100000000000000000000", "-4294967296", "-3", "0", "5",
+                                "4294967296", "100000000000000000000"]);
+        assert!(BigInt::from_i64(-1) < BigInt::zero());
+        assert!(BigInt::from_str("-0").unwrap() == BigInt::zero());
+    }
+
+    #[test]
+    fn test_abs_signum() {
+        let a = BigInt::from_i64(-42);
+        assert_eq!(a.abs(), BigInt::from_u64(42));
+        assert_eq!(a.signum(), BigInt::from_i64(-1));
+        assert_eq!(BigInt::zero().signum(), BigInt::zero());
+        assert_eq!(BigInt::from_u64(42).signum(), BigInt::one());
+        assert!(BigInt::one().is_positive());
+        assert!(!BigInt::zero().is_positive());
+    }
+
+    #[test]
+    fn test_from_i64() {
+        assert_eq!(format!("{}", BigInt::from_i64(i64::MIN)), "-9223372036854775808");
+        assert_eq!(BigInt::from_i64(0), BigInt::zero());
+    }
+
+    #[test]
+    fn test_from_str_sign_and_errors() {
+        assert_eq!("+42".parse::<BigInt>(), Ok(BigInt::from_u64(42)));
+        assert_eq!("-0".parse::<BigInt>(), Ok(BigInt::zero()));
+
+        let err = "-12x4".parse::<BigInt>().unwrap_err();
+        assert_eq!(err.column, Some(4));
+        assert_eq!(err.to_string(),
+                   "line 1, column 4: expected an integer, found \"-12x4\": invalid digit 'x'");
+        assert!("".parse::<BigInt>().is_err());
+        assert!("-".parse::<BigInt>().is_err());
+        assert!("1 2".parse::<BigInt>().is_err());
+    }
+
+    #[test]
+    fn test_scan_big_ints() {
+        let mut scan = Scanner::new("3\n1 99999999999999999999 -5\n2\n7 8z\n".as_bytes());
+        let v: Vec<BigInt> = scan.prefixed_vec().unwrap();
+        assert_eq!(v.iter().sum::<BigInt>(), "99999999999999999995".parse::<BigInt>().unwrap());
+
+        let err = scan.prefixed_vec::<BigInt>().unwrap_err();
+        assert_eq!((err.line, err.column), (Some(4), Some(4)));
+    }
+
+    #[test]
+    fn test_primitive_conversions() {
+        macro_rules! round_trip {
+            ($($t:ty)*) => {$(
+                for &n in &[<$t>::MIN, <$t>::MAX, 0, 1] {
+                    assert_eq!(<$t>::try_from(&BigInt::from(n)), Ok(n));
+                    assert_eq!(BigInt::from(n).to_string(), n.to_string());
+                }
+                let too_big = BigInt::from(<$t>::MAX) + BigInt::one();
+                assert!(<$t>::try_from(&too_big).is_err());
+                let too_small = BigInt::from(<$t>::MIN) - BigInt::one();
+                assert!(<$t>::try_from(&too_small).is_err());
+            )*};
+        }
+        round_trip!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+        assert_eq!(u8::try_from(&BigInt::from(-0i32)), Ok(0));
+        assert!(u128::try_from(&(BigInt::one() << 128)).is_err());
+    }
+
+    #[test]
+    fn test_operators_by_reference() {
+        let mut rng = Rng(0x5eed);
+        for _ in 0..200 {
+            let (a, b) = (rng.next_i128() >> 66, rng.next_i128() >> 70);
+            let (x, y) = (big(a), big(b));
+            assert_eq!(&x + &y, big(a + b));
+            assert_eq!(&x - y.clone(), big(a - b));
+            assert_eq!(x.clone() * &y, big(a * b));
+            assert_eq!(-&x, big(-a));
+            if b != 0 {
+                assert_eq!(&x / &y, big(a / b));
+                assert_eq!(&x % &y, big(a % b));
+            }
+
+            let mut z = x.clone();
+            z += &y;
+            z -= y.clone();
+            assert_eq!(z, x);
+            z *= &y;
+            z *= 3;
+            assert_eq!(z, big(a * b * 3));
+            if b != 0 {
+                z /= &y;
+                assert_eq!(z, big(a * 3));
+                z %= y.clone();
+                assert_eq!(z, big(a * 3 % b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let v: Vec<BigInt> = (1..26u64).map(BigInt::from).collect();
+        assert_eq!(v.iter().sum::<BigInt>(), BigInt::from(325u64));
+        assert_eq!(v.iter().product::<BigInt>(),
+                   "15511210043330985984000000".parse::<BigInt>().unwrap());
+        assert_eq!(v.into_iter().rev().product::<BigInt>(),
+                   "15511210043330985984000000".parse::<BigInt>().unwrap());
+        assert_eq!(Vec::<BigInt>::new().into_iter().sum::<BigInt>(), BigInt::zero());
+    }
+
+    /// Wide values from the shared test generator, so the property tests
+    /// below are reproducible without any dependencies.
+    struct Rng(u64);
+
+    impl Rng {
+        /// Two draws' worth of high bits, so every bit is usable.
+        fn next(&mut self) -> u64 {
+            let high = next_random(&mut self.0) >> 32;
+            high << 32 | next_random(&mut self.0) >> 32
+        }
+
+        /// A value with a random number of significant bits, so that small,
+        /// single-limb and multi-limb operands are all common.
+        fn next_u128(&mut self) -> u128 {
+            let n = (self.next() as u128) << 64 | self.next() as u128;
+            n >> (self.next() % 128)
+        }
+
+        fn next_i128(&mut self) -> i128 {
+            let n = (self.next_u128() >> 1) as i128;
+            if self.next() & 1 == 1 { -n } else { n }
+        }
+    }
+
+    fn big(n: i128) -> BigInt {
+        BigInt::from_str(&n.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_div_rem_matches_i128() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..10000 {
+            let a = rng.next_i128();
+            let b = rng.next_i128();
+            if b == 0 {
+                continue;
+            }
+            let (q, r) = big(a).div_rem(&big(b));
+            assert_eq!((q.clone(), r.clone()), (big(a / b), big(a % b)), "{} / {}", a, b);
+            assert_eq!(big(a) / big(b), q);
+            assert_eq!(big(a) % big(b), r);
+        }
+    }
+
+    #[test]
+    fn test_div_rem_large() {
+        let a = BigInt::from_str("15511210043330985984000000").unwrap();
+        let b = BigInt::from_str("479001600").unwrap();
+        assert_eq!(a.div_rem(&b), (BigInt::from_str("32382376266240000").unwrap(), BigInt::zero()));
+
+        let a = BigInt::from_u64(2).pow(500) + BigInt::from_u64(12345);
+        let b = BigInt::from_u64(2).pow(250);
+        assert_eq!(a.div_rem(&b), (b.clone(), BigInt::from_u64(12345)));
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn test_div_by_zero() {
+        let _ = BigInt::one() / BigInt::zero();
+    }
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(BigInt::from_u64(2).pow(100), BigInt::from_str("1267650600228229401496703205376").unwrap());
+        assert_eq!(BigInt::from_i64(-3).pow(3), BigInt::from_i64(-27));
+        assert_eq!(BigInt::from_i64(-3).pow(0), BigInt::one());
+        assert_eq!(BigInt::zero().pow(0), BigInt::one());
+
+        let mut rng = Rng(7);
+        for _ in 0..1000 {
+            let base = (rng.next() % 2000) as i128 - 1000;
+            let exp = (rng.next() % 12) as u32;
+            assert_eq!(big(base).pow(exp), big(base.pow(exp)));
+        }
+    }
+
+    #[test]
+    fn test_modpow_matches_u128() {
+        fn modpow(mut base: u128, mut exp: u128, modulus: u128) -> u128 {
+            let mut acc = 1 % modulus;
+            base %= modulus;
+            while exp > 0 {
+                if exp & 1 == 1 {
+                    acc = acc * base % modulus;
+                }
+                base = base * base % modulus;
+                exp >>= 1;
+            }
+            acc
+        }
+
+        let mut rng = Rng(42);
+        for _ in 0..2000 {
+            let base = rng.next_u128() >> 1;
+            let exp = rng.next_u128() >> 1;
+            let modulus = rng.next() as u128 >> 1 | 1;
+            assert_eq!(big(base as i128).modpow(&big(exp as i128), &big(modulus as i128)),
+                       big(modpow(base, exp, modulus) as i128));
+        }
+
+        assert_eq!(BigInt::from_i64(-2).modpow(&BigInt::from_u64(3), &BigInt::from_u64(5)),
+                   BigInt::from_u64(2));
+        assert_eq!(BigInt::from_u64(5).modpow(&BigInt::zero(), &BigInt::one()), BigInt::zero());
+    }
+
+    #[test]
+    fn test_gcd_matches_u128() {
+        fn gcd(a: u128, b: u128) -> u128 {
+            if b == 0 { a } else { gcd(b, a % b) }
+        }
+
+        let mut rng = Rng(99);
+        for _ in 0..2000 {
+            let common = (rng.next() % 100000) as i128;
+            let a = (rng.next_i128() >> 20) * common;
+            let b = (rng.next_i128() >> 20) * common;
+            assert_eq!(big(a).gcd(&big(b)), big(gcd(a.unsigned_abs(), b.unsigned_abs()) as i128));
+        }
+        assert_eq!(BigInt::zero().gcd(&BigInt::zero()), BigInt::zero());
+    }
+
+    #[test]
+    fn test_isqrt_matches_u128() {
+        let mut rng = Rng(1234);
+        for _ in 0..2000 {
+            let n = rng.next_u128() >> 1;
+            let root = big(n as i128).isqrt();
+            let r = format!("{}", root).parse::<u128>().unwrap();
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({}) = {}", n, r);
+        }
+        assert_eq!(BigInt::zero().isqrt(), BigInt::zero());
+        assert_eq!(BigInt::from_u64(10).pow(100).isqrt(), BigInt::from_u64(10).pow(50));
+    }
+
+    #[test]
+    fn test_display() {
+        let expected = "12345".to_string();
+        let actual = format!("{}", BigInt::from_str("12345").unwrap());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for s in &["0", "1", "999999999", "1000000000", "4294967296",
+                   "1000000000000000000000000000001",
+                   "15511210043330985984000000", "-1", "-1000000000"] {
+            assert_eq!(format!("{}", BigInt::from_str(s).unwrap()), *s);
+        }
+    }
+}
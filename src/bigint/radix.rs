@@ -0,0 +1,349 @@
+//! Conversion between `BigInt`s and strings of digits in any radix from 2 to
+//! 36, and the `std::fmt` traits built on it.
+//!
+//! Negative numbers are always written as a `-` and the magnitude, including
+//! in hex, octal and binary: there is no fixed width to take a two's
+//! complement in.
+
+use std::fmt::{Binary, Display, Error, Formatter, LowerExp, LowerHex, Octal, UpperExp, UpperHex};
+use std::str;
+
+use error::InputError;
+use super::{BigInt, limbs};
+
+/// The largest power of `radix` that fits in a limb, and how many digits it
+/// has. Conversions work a chunk of that many digits at a time.
+fn chunk(radix: u32) -> (u32, usize) {
+    let mut base = radix;
+    let mut digits = 1;
+    while let Some(next) = base.checked_mul(radix) {
+        base = next;
+        digits += 1;
+    }
+    (base, digits)
+}
+
+fn check_radix(radix: u32) {
+    assert!((2..=36).contains(&radix), "radix must be in 2..=36, got {}", radix);
+}
+
+/// Append the digits of `n` in `radix`, zero-padded to at least `width`.
+fn push_digits(s: &mut String, mut n: u32, radix: u32, width: usize) {
+    let mut buf = [b'0'; 32];
+    let mut start = buf.len();
+    while n > 0 {
+        start -= 1;
+        buf[start] = b"0123456789abcdefghijklmnopqrstuvwxyz"[(n % radix) as usize];
+        n /= radix;
+    }
+    let start = start.min(buf.len() - width);
+    s.push_str(str::from_utf8(&buf[start..]).unwrap());
+}
+
+/// Decimal `digits` as `d.ddd` followed by `e` and the exponent. Without a
+/// precision every significant digit is kept; with one the fraction is
+/// truncated or zero-padded to that many digits.
+fn exponential(digits: &str, precision: Option<usize>, e: char) -> String {
+    let fraction = &digits[1..];
+    let fraction = match precision {
+        None => fraction.trim_end_matches('0').to_string(),
+        Some(p) if p <= fraction.len() => fraction[..p].to_string(),
+        Some(p) => format!("{:0<width$}", fraction, width = p),
+    };
+    let point = if fraction.is_empty() { "" } else { "." };
+    format!("{}{}{}{}{}", &digits[..1], point, fraction, e, digits.len() - 1)
+}
+
+/// `digits` rounded half to even to `keep` significant digits, then padded
+/// back to their original length with zeros, so the exponent still comes
+/// from the length. Carrying out of the top digit makes it one longer.
+fn round_digits(digits: &str, keep: usize) -> String {
+    if keep >= digits.len() {
+        return digits.to_string();
+    }
+    let (kept, dropped) = digits.split_at(keep);
+    let mut kept = kept.as_bytes().to_vec();
+    let first = dropped.as_bytes()[0];
+    let beyond_half = dropped[1..].bytes().any(|d| d != b'0');
+    let odd = kept.last().is_some_and(|d| (d - b'0') % 2 == 1);
+    if first > b'5' || first == b'5' && (beyond_half || odd) {
+        let mut i = kept.len();
+        loop {
+            if i == 0 {
+                kept.insert(0, b'1');
+                break;
+            }
+            i -= 1;
+            if kept[i] == b'9' {
+                kept[i] = b'0';
+            } else {
+                kept[i] += 1;
+                break;
+            }
+        }
+    }
+    let mut rounded = String::from_utf8(kept).unwrap();
+    let len = digits.len() + rounded.len() - keep;
+    rounded.extend(std::iter::repeat_n('0', len - rounded.len()));
+    rounded
+}
+
+/// `{:e}` with an optional precision, rounding half to even like the
+/// primitive integers do.
+fn fmt_exponential(n: &BigInt, f: &mut Formatter, e: char) -> Result<(), Error> {
+    let mut digits = n.magnitude_str(10);
+    if let Some(p) = f.precision() {
+        digits = round_digits(&digits, p + 1);
+    }
+    f.pad_integral(!n.negative, "", &exponential(&digits, f.precision(), e))
+}
+
+impl BigInt {
+    /// Parse digits in `radix`, in either case, with an optional leading `+`
+    /// or `-`. Panics if `radix` is not in `2..=36`.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<BigInt, InputError> {
+        check_radix(radix);
+        let expected = if radix == 10 {
+            "an integer".to_string()
+        } else {
+            format!("a base-{} integer", radix)
+        };
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if digits.is_empty() {
+            return Err(InputError::new(s, expected).because("no digits"));
+        }
+        if let Some(i) = digits.find(|c: char| !c.is_digit(radix)) {
+            let c = digits[i..].chars().next().unwrap();
+            let column = s.len() - digits.len() + i + 1;
+            return Err(InputError::new(s, expected).at(1, column)
+                       .because(format!("invalid digit {:?}", c)));
+        }
+
+        let chunk_len = chunk(radix).1;
+        let mut limbs = vec![];
+        let head = digits.len() % chunk_len;
+        let mut start = 0;
+        let mut end = if head == 0 { chunk_len } else { head };
+        while start < digits.len() {
+            let chunk = u32::from_str_radix(&digits[start..end], radix).unwrap();
+            limbs::mul_add_small(&mut limbs, radix.pow((end - start) as u32), chunk);
+            start = end;
+            end += chunk_len;
+        }
+        Ok(BigInt::from_parts(negative, limbs))
+    }
+
+    /// The digits of the magnitude in `radix`, lowercase.
+    fn magnitude_str(&self, radix: u32) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+
+        // Peel off a chunk of digits at a time, least significant first.
+        let (base, chunk_len) = chunk(radix);
+        let mut n = self.limbs.clone();
+        let mut chunks = vec![];
+        while !n.is_empty() {
+            chunks.push(limbs::div_rem_small(&mut n, base));
+        }
+
+        let mut s = String::with_capacity(chunks.len() * chunk_len);
+        let mut chunks = chunks.iter().rev();
+        push_digits(&mut s, *chunks.next().unwrap(), radix, 0);
+        for &c in chunks {
+            push_digits(&mut s, c, radix, chunk_len);
+        }
+        s
+    }
+
+    /// The digits in `radix`, lowercase, after a `-` if negative. Panics if
+    /// `radix` is not in `2..=36`.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        check_radix(radix);
+        let sign = if self.negative { "-" } else { "" };
+        format!("{}{}", sign, self.magnitude_str(radix))
+    }
+
+    /// Like `to_str_radix`, with `separator` between every `group` digits
+    /// counting from the right, as in `1,234,567`.
+    pub fn to_grouped_string(&self, radix: u32, group: usize, separator: char) -> String {
+        check_radix(radix);
+        assert!(group > 0, "digit groups must not be empty");
+        let digits = self.magnitude_str(radix);
+        let mut s = String::with_capacity(digits.len() * 2);
+        if self.negative {
+            s.push('-');
+        }
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(group) {
+                s.push(separator);
+            }
+            s.push(c);
+        }
+        s
+    }
+
+    /// Scientific notation with the mantissa truncated, not rounded, to
+    /// `precision` decimal places: 25! to four places is `1.5511e25`.
+    pub fn to_scientific(&self, precision: usize) -> String {
+        let sign = if self.negative { "-" } else { "" };
+        format!("{}{}", sign, exponential(&self.magnitude_str(10), Some(precision), 'e'))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.pad_integral(!self.negative, "", &self.magnitude_str(10))
+    }
+}
+
+macro_rules! impl_radix_fmt {
+    ($($imp:ident, $radix:expr, $prefix:expr, $upper:expr;)*) => {$(
+        impl $imp for BigInt {
+            fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                let mut digits = self.magnitude_str($radix);
+                if $upper {
+                    digits.make_ascii_uppercase();
+                }
+                f.pad_integral(!self.negative, $prefix, &digits)
+            }
+        }
+    )*};
+}
+
+impl_radix_fmt! {
+    LowerHex, 16, "0x", false;
+    UpperHex, 16, "0x", true;
+    Octal, 8, "0o", false;
+    Binary, 2, "0b", false;
+}
+
+/// `{:e}` keeps every significant digit; `{:.4e}` rounds the mantissa to
+/// four places, half to even, unlike `to_scientific`.
+impl LowerExp for BigInt {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        fmt_exponential(self, f, 'e')
+    }
+}
+
+impl UpperExp for BigInt {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        fmt_exponential(self, f, 'E')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BigInt;
+    use test_support::next_random_u128;
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_matches_primitive_formatting() {
+        let mut seed = 1;
+        for i in 0..200 {
+            let n = next_random_u128(&mut seed) >> (i % 128);
+            let b = BigInt::from(n);
+            assert_eq!(format!("{:x} {:X} {:o} {:b}", b, b, b, b),
+                       format!("{:x} {:X} {:o} {:b}", n, n, n, n));
+            assert_eq!(format!("{:#x} {:#b} {:#o}", b, b, b), format!("{:#x} {:#b} {:#o}", n, n, n));
+            assert_eq!(format!("{:>50} {:<50x} {:050}", b, b, b), format!("{:>50} {:<50x} {:050}", n, n, n));
+            assert_eq!(format!("{:e} {:E}", b, b), format!("{:e} {:E}", n, n));
+            for radix in 2..37 {
+                let s = b.to_str_radix(radix);
+                assert_eq!(u128::from_str_radix(&s, radix), Ok(n));
+                assert_eq!(BigInt::from_str_radix(&s.to_uppercase(), radix), Ok(b.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_negative() {
+        let n = BigInt::from(-255);
+        assert_eq!(format!("{:x} {:#X} {:o} {:b}", n, n, n, n), "-ff -0xFF -377 -11111111");
+        assert_eq!(format!("{:08} {:>6}", n, n), "-0000255   -255");
+        assert_eq!(n.to_str_radix(36), "-73");
+        assert_eq!(BigInt::from_str_radix("-73", 36), Ok(n));
+    }
+
+    #[test]
+    fn test_round_trip_every_radix() {
+        let n = BigInt::from(3).pow(500) - BigInt::from(1u64 << 40);
+        for radix in 2..37 {
+            let s = n.to_str_radix(radix);
+            assert_eq!(BigInt::from_str_radix(&s, radix), Ok(n.clone()), "radix {}", radix);
+        }
+        assert_eq!(n.to_str_radix(10), n.to_string());
+        assert_eq!(BigInt::zero().to_str_radix(2), "0");
+    }
+
+    #[test]
+    fn test_from_str_radix_errors() {
+        assert_eq!(BigInt::from_str_radix("+Zz", 36), Ok(BigInt::from(36 * 35 + 35)));
+        let err = BigInt::from_str_radix("1f2g", 16).unwrap_err();
+        assert_eq!(err.to_string(),
+                   "line 1, column 4: expected a base-16 integer, found \"1f2g\": invalid digit 'g'");
+        assert!(BigInt::from_str_radix("102", 2).is_err());
+        assert!(BigInt::from_str_radix("-", 8).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must be in 2..=36")]
+    fn test_radix_out_of_range() {
+        BigInt::one().to_str_radix(37);
+    }
+
+    #[test]
+    fn test_grouped() {
+        assert_eq!(BigInt::from(1234567).to_grouped_string(10, 3, ','), "1,234,567");
+        assert_eq!(BigInt::from(-100000).to_grouped_string(10, 3, ','), "-100,000");
+        assert_eq!(BigInt::from(999).to_grouped_string(10, 3, ','), "999");
+        assert_eq!(BigInt::from(0xdead_beef_u32).to_grouped_string(16, 4, '_'), "dead_beef");
+        assert_eq!(BigInt::from(10).to_grouped_string(2, 2, ' '), "10 10");
+    }
+
+    #[test]
+    fn test_scientific() {
+        let n = big("15511210043330985984000000");
+        assert_eq!(n.to_scientific(4), "1.5511e25");
+        assert_eq!(n.to_scientific(0), "1e25");
+        assert_eq!((-n.clone()).to_scientific(2), "-1.55e25");
+        assert_eq!(format!("{:e}", n), "1.5511210043330985984e25");
+        assert_eq!(format!("{:.3E}", n), "1.551E25");
+        assert_eq!(big("999").to_scientific(1), "9.9e2");
+        assert_eq!(big("12").to_scientific(5), "1.20000e1");
+        assert_eq!(BigInt::zero().to_scientific(2), "0.00e0");
+        assert_eq!(format!("{:.1e}", big("999")), "1.0e3");
+        assert_eq!(format!("{:.2e}", -big("99951")), "-1.00e5");
+    }
+
+    #[test]
+    fn test_rounded_exponential_matches_primitive() {
+        let mut seed = 7;
+        let mut values = vec![0, 5, 15, 25, 125, 135, 995, 999, 1250, 1251, 9950, u64::MAX as u128, u128::MAX];
+        for i in 0..300 {
+            let n = next_random_u128(&mut seed) >> (i % 128);
+            values.push(n);
+            // Ties: a digit and a five, then zeros.
+            values.push((n % 1000 * 10 + 5) * 10u128.pow((i % 20) as u32));
+        }
+        for &n in &values {
+            let b = BigInt::from(n);
+            for p in 0..42 {
+                assert_eq!(format!("{:.*e}", p, b), format!("{:.*e}", p, n), "{} to {} places", n, p);
+                if n > 0 {
+                    assert_eq!(format!("{:.*E}", p, -b.clone()), format!("-{:.*E}", p, n));
+                }
+            }
+            let small = n as u64;
+            assert_eq!(format!("{:.3e}", BigInt::from(small)), format!("{:.3e}", small));
+        }
+    }
+}
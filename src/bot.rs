@@ -0,0 +1,199 @@
+//! The protocol shared by the bot-building challenges that want one move
+//! per run: read where the bot is and what the board looks like, print
+//! what it does next.
+
+use std::fmt;
+use std::io::BufRead;
+
+use error::InputError;
+use grid::{Board, Coord, Direction};
+use scanner::Scanner;
+
+/// Something a bot can do on its turn.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Action {
+    Move(Direction),
+    /// Clean the cell the bot is on.
+    Clean,
+    /// Do nothing. No challenge asks for this, but it's what a bot with
+    /// nothing left to do is doing.
+    Stay,
+}
+
+impl From<Direction> for Action {
+    fn from(dir: Direction) -> Action {
+        Action::Move(dir)
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Action::Move(dir) => write!(fmt, "{}", dir),
+            Action::Clean => fmt.write_str("CLEAN"),
+            Action::Stay => fmt.write_str("STAY"),
+        }
+    }
+}
+
+/// How a challenge says how big its board is.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Size {
+    /// `n` on a line of its own before the bot's position, for an `n` by
+    /// `n` board, as in "Save Princess 2".
+    Leading,
+    /// `rows cols` on the line after the bot's position, as in "BotClean
+    /// Large".
+    Trailing,
+    /// Not at all: the board always has this many rows, as in "BotClean".
+    Fixed(usize),
+}
+
+/// What a bot gets to see on its turn.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BoardView {
+    board: Board,
+    position: Coord,
+}
+
+impl BoardView {
+    /// Fails if `position` is off the board.
+    pub fn new(board: Board, position: Coord) -> Result<BoardView, InputError> {
+        if !board.contains(position) {
+            let found = format!("{} {}", position.y, position.x);
+            return Err(InputError::new(&found, format!("a position on the {}x{} board",
+                                                       board.height(), board.width())));
+        }
+        Ok(BoardView { board, position })
+    }
+
+    /// Read the bot's position, as `row column`, and then the board.
+    ///
+    /// Fails if the board isn't as wide as its header says.
+    pub fn read<R: BufRead>(scan: &mut Scanner<R>, size: Size) -> Result<BoardView, InputError> {
+        let leading = match size {
+            Size::Leading => Some((scan.read()?, scan.position())),
+            _ => None,
+        };
+        let (row, column): (i32, i32) = scan.tuple()?;
+        let (board, header): (Board, _) = match size {
+            Size::Leading => {
+                let (n, at) = leading.unwrap();
+                (scan.block(n)?, Some((n, at)))
+            },
+            Size::Fixed(rows) => (scan.block(rows)?, None),
+            Size::Trailing => {
+                let rows = scan.read()?;
+                let columns = scan.read()?;
+                let at = scan.position();
+                (scan.block(rows)?, Some((columns, at)))
+            },
+        };
+        if let Some((columns, (line, col))) = header {
+            if board.width() != columns {
+                let expected = format!("rows of {} cells", columns);
+                return Err(InputError::new(&board.width().to_string(), expected).at(line, col));
+            }
+        }
+        BoardView::new(board, Coord::new(column, row))
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Where the bot is. The board may show something else there, like
+    /// the dirt under it.
+    pub fn position(&self) -> Coord {
+        self.position
+    }
+}
+
+/// A player in a one-move-per-run challenge.
+pub trait Bot {
+    fn next_move(&mut self, view: &BoardView) -> Action;
+}
+
+/// Play one turn against stdin and print the move.
+pub fn play<B: Bot>(mut bot: B, size: Size) {
+    ::run(|scan| {
+        let view = BoardView::read(scan, size)?;
+        println!("{}", bot.next_move(&view));
+        Ok(())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, BoardView, Bot, Size};
+    use grid::{Cell, Coord, Direction};
+    use scanner::Scanner;
+
+    fn read(input: &str, size: Size) -> BoardView {
+        BoardView::read(&mut Scanner::new(input.as_bytes()), size).unwrap()
+    }
+
+    #[test]
+    fn test_read() {
+        let leading = read("3\n1 2\n---\n--m\n---\n", Size::Leading);
+        assert_eq!(leading.position(), Coord::new(2, 1));
+        assert_eq!(leading.board()[leading.position()], Cell::Bot);
+
+        let fixed = read("0 0\nbd\n-d\n", Size::Fixed(2));
+        assert_eq!(fixed.position(), Coord::new(0, 0));
+        assert_eq!(fixed.board().positions(|c| c == Cell::Dirty).len(), 2);
+
+        let trailing = read("2 0\n3 4\n----\n-d--\nd---\n", Size::Trailing);
+        assert_eq!(trailing.position(), Coord::new(0, 2));
+        assert_eq!(trailing.board().height(), 3);
+        assert_eq!(trailing.board()[trailing.position()], Cell::Dirty);
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let mut scan = Scanner::new("3 0\n---\n-m-\n---\n".as_bytes());
+        let err = BoardView::read(&mut scan, Size::Fixed(3)).unwrap_err();
+        assert_eq!(err.token, Some("3 0".to_string()));
+
+        let mut scan = Scanner::new("0 0\n2 3\n--\n--\n".as_bytes());
+        let err = BoardView::read(&mut scan, Size::Trailing).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(3)));
+        assert_eq!(err.token, Some("2".to_string()));
+
+        let mut scan = Scanner::new(" 3\n0 0\n--\n--\nm-\n".as_bytes());
+        let err = BoardView::read(&mut scan, Size::Leading).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(1), Some(2)));
+        assert_eq!(err.expected, Some("rows of 3 cells".to_string()));
+
+        let mut scan = Scanner::new("0 0\n--\n-x\n".as_bytes());
+        let err = BoardView::read(&mut scan, Size::Fixed(2)).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(3), Some(2)));
+
+        let mut scan = Scanner::new("0 0\n--\n".as_bytes());
+        assert!(BoardView::read(&mut scan, Size::Fixed(2)).is_err());
+    }
+
+    /// Walks right until it can't, then stays put.
+    struct Rightwards;
+
+    impl Bot for Rightwards {
+        fn next_move(&mut self, view: &BoardView) -> Action {
+            let next = view.position().step(Direction::Right);
+            match view.board().get(next) {
+                Some(Cell::Wall) | None => Action::Stay,
+                Some(_) => Direction::Right.into(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_bot() {
+        let mut bot = Rightwards;
+        assert_eq!(bot.next_move(&read("0 0\nm-#\n", Size::Fixed(1))), Action::Move(Direction::Right));
+        assert_eq!(bot.next_move(&read("0 1\n-m#\n", Size::Fixed(1))), Action::Stay);
+        assert_eq!(bot.next_move(&read("0 2\n--m\n", Size::Fixed(1))), Action::Stay);
+
+        assert_eq!(Action::from(Direction::Up).to_string(), "UP");
+        assert_eq!(Action::Clean.to_string(), "CLEAN");
+    }
+}
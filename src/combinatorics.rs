@@ -0,0 +1,277 @@
+//! Counting: binomials, Catalan, Stirling and derangement numbers.
+//!
+//! There are two backends. `FactorialTable` precomputes factorials modulo a
+//! prime, after which binomials are a couple of multiplications each. The
+//! exact ones return `BigInt`s and build on `factorial`, which also has the
+//! exact `binomial` and `multinomial`. The tables of Stirling and
+//! derangement numbers work in any `Ring`, so they cover both.
+
+use bigint::BigInt;
+use factorial::{binomial, product_range};
+use modint::ModInt;
+use ring::Ring;
+
+/// `n!` and `1/n!` modulo the prime `M`, for every `n` up to a limit.
+pub struct FactorialTable<const M: u64> {
+    fact: Vec<ModInt<M>>,
+    inv_fact: Vec<ModInt<M>>,
+}
+
+impl<const M: u64> FactorialTable<M> {
+    /// Tabulate up to and including `n`, which must be below `M`: `M!` and
+    /// beyond are zero and have no inverse.
+    pub fn new(n: usize) -> FactorialTable<M> {
+        assert!((n as u64) < M, "factorials up to {} include 0 mod {}", n, M);
+        let mut fact = Vec::with_capacity(n + 1);
+        fact.push(ModInt::new(1));
+        for i in 1..=n {
+            fact.push(fact[i - 1] * ModInt::from(i));
+        }
+        // One inverse, then walk back down: 1/(i-1)! = i/i!.
+        let mut inv_fact = vec![ModInt::new(0); n + 1];
+        inv_fact[n] = fact[n].inv().expect("modulus must be prime");
+        for i in (1..=n).rev() {
+            inv_fact[i - 1] = inv_fact[i] * ModInt::from(i);
+        }
+        FactorialTable { fact, inv_fact }
+    }
+
+    /// The largest `n` tabulated.
+    pub fn limit(&self) -> usize {
+        self.fact.len() - 1
+    }
+
+    pub fn factorial(&self, n: usize) -> ModInt<M> {
+        self.fact[self.check(n)]
+    }
+
+    pub fn inv_factorial(&self, n: usize) -> ModInt<M> {
+        self.inv_fact[self.check(n)]
+    }
+
+    /// `n choose k`, zero if `k > n`.
+    pub fn ncr(&self, n: usize, k: usize) -> ModInt<M> {
+        if k > n {
+            return ModInt::new(0);
+        }
+        self.factorial(n) * self.inv_fact[k] * self.inv_fact[n - k]
+    }
+
+    /// `n! / (n - k)!`, the ways to pick `k` of `n` in order; zero if
+    /// `k > n`.
+    pub fn npr(&self, n: usize, k: usize) -> ModInt<M> {
+        if k > n {
+            return ModInt::new(0);
+        }
+        self.factorial(n) * self.inv_fact[n - k]
+    }
+
+    /// `(k1 + k2 + …)! / (k1! · k2! · …)`.
+    pub fn multinomial(&self, ks: &[usize]) -> ModInt<M> {
+        let n = ks.iter().sum();
+        ks.iter().fold(self.factorial(n), |acc, &k| acc * self.inv_fact[k])
+    }
+
+    /// The `n`th Catalan number, `(2n choose n) / (n + 1)`. Needs the table
+    /// to reach `max(2n, n + 1)`.
+    pub fn catalan(&self, n: usize) -> ModInt<M> {
+        self.factorial(2 * n) * self.inv_factorial(n + 1) * self.inv_fact[n]
+    }
+
+    /// `n choose k` for any `n`, by Lucas' theorem: the product of the
+    /// binomials of the base-`M` digits of `n` and `k`. Needs the table to
+    /// reach `M - 1`, so it's for small primes and huge `n`.
+    pub fn ncr_lucas(&self, mut n: u64, mut k: u64) -> ModInt<M> {
+        self.check(M as usize - 1);
+        let mut acc = ModInt::new(1);
+        while k > 0 {
+            acc *= self.ncr((n % M) as usize, (k % M) as usize);
+            n /= M;
+            k /= M;
+        }
+        acc
+    }
+
+    fn check(&self, n: usize) -> usize {
+        assert!(n <= self.limit(), "{}! is beyond the table's {}", n, self.limit());
+        n
+    }
+}
+
+/// `n! / (n - k)!` exactly; zero if `k > n`.
+pub fn npr(n: u64, k: u64) -> BigInt {
+    if k > n {
+        return BigInt::zero();
+    }
+    product_range(n - k + 1, n + 1)
+}
+
+/// The `n`th Catalan number exactly.
+pub fn catalan(n: u64) -> BigInt {
+    binomial(2 * n, n) / BigInt::from(n + 1)
+}
+
+/// `n` as an element of `T`, by doubling.
+fn from_count<T: Ring>(n: usize) -> T {
+    let mut acc = T::zero();
+    for i in (0..usize::BITS - n.leading_zeros()).rev() {
+        acc = acc.clone() + acc;
+        if (n >> i) & 1 == 1 {
+            acc = acc + T::one();
+        }
+    }
+    acc
+}
+
+/// Unsigned Stirling numbers of the first kind, `c(i, j)` at `[i][j]` for
+/// `j <= i <= n`: the permutations of `i` items with `j` cycles.
+pub fn stirling_first<T: Ring>(n: usize) -> Vec<Vec<T>> {
+    let mut rows: Vec<Vec<T>> = vec![vec![T::one()]];
+    for i in 1..=n {
+        // c(i, j) = (i - 1) c(i - 1, j) + c(i - 1, j - 1)
+        let above = &rows[i - 1];
+        let factor: T = from_count(i - 1);
+        let row = (0..=i).map(|j| {
+            let stay = if j < i { factor.clone() * above[j].clone() } else { T::zero() };
+            if j > 0 { stay + above[j - 1].clone() } else { stay }
+        }).collect();
+        rows.push(row);
+    }
+    rows
+}
+
+/// Stirling numbers of the second kind, `S(i, j)` at `[i][j]` for
+/// `j <= i <= n`: the ways to partition `i` items into `j` nonempty sets.
+pub fn stirling_second<T: Ring>(n: usize) -> Vec<Vec<T>> {
+    let mut rows: Vec<Vec<T>> = vec![vec![T::one()]];
+    for i in 1..=n {
+        // S(i, j) = j S(i - 1, j) + S(i - 1, j - 1)
+        let above = &rows[i - 1];
+        let row = (0..=i).map(|j| {
+            let stay = if j < i { from_count::<T>(j) * above[j].clone() } else { T::zero() };
+            if j > 0 { stay + above[j - 1].clone() } else { stay }
+        }).collect();
+        rows.push(row);
+    }
+    rows
+}
+
+/// The derangement numbers `D(0)` to `D(n)`: permutations of `i` items
+/// that move every one of them.
+pub fn derangements<T: Ring>(n: usize) -> Vec<T> {
+    let mut d = vec![T::one(), T::zero()];
+    for i in 2..=n {
+        // D(i) = (i - 1) (D(i - 1) + D(i - 2))
+        let next = from_count::<T>(i - 1) * (d[i - 1].clone() + d[i - 2].clone());
+        d.push(next);
+    }
+    d.truncate(n + 1);
+    d
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FactorialTable, catalan, derangements, npr, stirling_first, stirling_second};
+    use bigint::BigInt;
+    use factorial::{binomial, factorial, multinomial};
+    use modint::{MOD_1E9_7, ModInt};
+    use test::Bencher;
+
+    type Mod = ModInt<MOD_1E9_7>;
+
+    fn reduce<const M: u64>(n: &BigInt) -> ModInt<M> {
+        n.to_string().parse().unwrap()
+    }
+
+    #[test]
+    fn test_table_against_pascal() {
+        let table = FactorialTable::<MOD_1E9_7>::new(400);
+        let mut row = vec![Mod::new(1)];
+        for n in 0..200 {
+            for k in 0..=n + 1 {
+                let expected = row.get(k).cloned().unwrap_or_default();
+                assert_eq!(table.ncr(n, k), expected, "{} choose {}", n, k);
+            }
+            row = (0..=n + 1).map(|k| {
+                let left = if k > 0 { row[k - 1] } else { Mod::new(0) };
+                left + row.get(k).cloned().unwrap_or_default()
+            }).collect();
+        }
+        assert_eq!(table.factorial(20), Mod::from(2_432_902_008_176_640_000u64));
+        assert_eq!(table.factorial(400) * table.inv_factorial(400), Mod::new(1));
+        assert_eq!(table.limit(), 400);
+    }
+
+    #[test]
+    fn test_against_exact() {
+        let table = FactorialTable::<MOD_1E9_7>::new(1000);
+        for &(n, k) in &[(0, 0), (5, 2), (10, 10), (300, 150), (1000, 1), (500, 499), (7, 9)] {
+            assert_eq!(table.ncr(n, k), reduce(&binomial(n as u64, k as u64)));
+            assert_eq!(table.npr(n, k), reduce(&npr(n as u64, k as u64)), "P({}, {})", n, k);
+        }
+        assert_eq!(npr(10, 3), BigInt::from(720));
+        assert_eq!(npr(3, 4), BigInt::zero());
+        assert_eq!(table.multinomial(&[100, 200, 300]), reduce(&multinomial(&[100, 200, 300])));
+
+        let first: Vec<BigInt> = (0..10).map(catalan).collect();
+        assert_eq!(first, [1, 1, 2, 5, 14, 42, 132, 429, 1430, 4862].map(BigInt::from));
+        for n in [0, 1, 17, 200, 499] {
+            assert_eq!(table.catalan(n), reduce(&catalan(n as u64)), "C({})", n);
+        }
+    }
+
+    #[test]
+    fn test_lucas() {
+        let small = FactorialTable::<13>::new(12);
+        for n in 0..300u64 {
+            for k in (0..=n + 2).step_by(7) {
+                assert_eq!(small.ncr_lucas(n, k), reduce(&binomial(n, k)), "{} choose {} mod 13", n, k);
+            }
+        }
+        // Kummer: 2 divides (n choose k) unless k's bits are a subset of n's.
+        let two = FactorialTable::<2>::new(1);
+        assert_eq!(two.ncr_lucas(u64::MAX, 12345).value(), 1);
+        assert_eq!(two.ncr_lucas(1 << 40, 3).value(), 0);
+        let p = FactorialTable::<10_007>::new(10_006);
+        assert_eq!(p.ncr_lucas(1_000_000_000_000, 10_007), ModInt::new(146));
+    }
+
+    #[test]
+    fn test_stirling_and_derangements() {
+        let first: Vec<Vec<BigInt>> = stirling_first(30);
+        let second: Vec<Vec<BigInt>> = stirling_second(30);
+        assert_eq!(first[5], [0, 24, 50, 35, 10, 1].map(BigInt::from));
+        assert_eq!(second[5], [0, 1, 15, 25, 10, 1].map(BigInt::from));
+        let bell: Vec<BigInt> = second.iter().take(8).map(|row| row.iter().sum()).collect();
+        assert_eq!(bell, [1, 1, 2, 5, 15, 52, 203, 877].map(BigInt::from));
+        for (n, row) in first.iter().enumerate() {
+            assert_eq!(row.iter().sum::<BigInt>(), factorial(n as u64));
+        }
+
+        let modular: Vec<Vec<Mod>> = stirling_second(30);
+        assert_eq!(modular[30][12], reduce(&second[30][12]));
+
+        let exact: Vec<BigInt> = derangements(40);
+        assert_eq!(exact[..8], [1, 0, 1, 2, 9, 44, 265, 1854].map(BigInt::from));
+        for (n, d) in exact.iter().enumerate() {
+            // Inclusion–exclusion: D(n) = Σ (-1)^k n! / k!.
+            let sum: BigInt = (0..=n as u64).map(|k| {
+                let term = product_of(k + 1, n as u64);
+                if k % 2 == 0 { term } else { -term }
+            }).sum();
+            assert_eq!(*d, sum, "D({})", n);
+        }
+        assert_eq!(derangements::<Mod>(40)[40], reduce(&exact[40]));
+        assert_eq!(derangements::<i64>(0), vec![1]);
+    }
+
+    /// `low · (low + 1) · … · high`.
+    fn product_of(low: u64, high: u64) -> BigInt {
+        (low..=high).map(BigInt::from).product()
+    }
+
+    #[bench]
+    fn bench_table_1e6(b: &mut Bencher) {
+        b.iter(|| FactorialTable::<MOD_1E9_7>::new(1_000_000));
+    }
+}
@@ -0,0 +1,455 @@
+//! Exact fixed-point decimals, for challenges that print a set number of
+//! decimal places.
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::{Display, Error, Formatter};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use bigint::BigInt;
+use error::InputError;
+use rational::Rational;
+
+/// How to round a value that falls between two representable decimals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// To the nearest, with ties away from zero: 2.5 becomes 3, -2.5 becomes
+    /// -3.
+    HalfUp,
+    /// To the nearest, with ties to the even neighbour: 2.5 becomes 2, 3.5
+    /// becomes 4. This is what `printf` and Rust's float formatting do.
+    HalfEven,
+    /// Towards negative infinity.
+    Floor,
+    /// Towards positive infinity.
+    Ceil,
+    /// Towards zero.
+    Truncate,
+}
+
+/// `n / d` rounded to an integer. `d` must be positive.
+fn div_round(n: &BigInt, d: &BigInt, mode: Rounding) -> BigInt {
+    let (q, r) = n.div_rem(d);
+    if r.is_zero() {
+        return q;
+    }
+    // The remainder has the sign of `n`, and so does the step away from zero.
+    let away = if n.is_negative() { q.clone() - BigInt::one() } else { q.clone() + BigInt::one() };
+    let half = (r.abs() << 1).cmp(d);
+    match mode {
+        Rounding::Truncate => q,
+        Rounding::Floor => if n.is_negative() { away } else { q },
+        Rounding::Ceil => if n.is_negative() { q } else { away },
+        Rounding::HalfUp => if half == Ordering::Less { q } else { away },
+        Rounding::HalfEven => match half {
+            Ordering::Less => q,
+            Ordering::Equal if !q.test_bit(0) => q,
+            _ => away,
+        },
+    }
+}
+
+/// The largest power of ten `from_str` will multiply out, and the largest
+/// scale an exponent can give: `1e1000000000` would take gigabytes, and so
+/// would comparing `1e-1000000000` with anything.
+const MAX_EXPONENT: u32 = 100_000;
+
+fn power_of_ten(exp: u32) -> BigInt {
+    BigInt::from(10).pow(exp)
+}
+
+/// A decimal number `mantissa / 10^scale`.
+///
+/// The scale is the number of digits after the point and is kept as given,
+/// so `1.50` and `1.5` print differently but compare equal.
+#[derive(Clone, Debug)]
+pub struct Decimal {
+    mantissa: BigInt,
+    scale: u32,
+}
+
+impl Decimal {
+    pub fn new(mantissa: BigInt, scale: u32) -> Decimal {
+        Decimal { mantissa, scale }
+    }
+
+    pub fn zero() -> Decimal {
+        Decimal::new(BigInt::zero(), 0)
+    }
+
+    pub fn one() -> Decimal {
+        Decimal::new(BigInt::one(), 0)
+    }
+
+    /// `r` rounded to `scale` decimal places.
+    pub fn from_rational(r: &Rational, scale: u32, mode: Rounding) -> Decimal {
+        Decimal::new(div_round(&(r.numer() * power_of_ten(scale)), r.denom(), mode), scale)
+    }
+
+    pub fn to_rational(&self) -> Rational {
+        Rational::new(self.mantissa.clone(), power_of_ten(self.scale))
+    }
+
+    pub fn mantissa(&self) -> &BigInt {
+        &self.mantissa
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.mantissa.is_negative()
+    }
+
+    pub fn abs(&self) -> Decimal {
+        Decimal::new(self.mantissa.abs(), self.scale)
+    }
+
+    /// The same value with `scale` decimal places, rounding if that drops
+    /// digits.
+    pub fn rescale(&self, scale: u32, mode: Rounding) -> Decimal {
+        if scale >= self.scale {
+            Decimal::new(&self.mantissa * power_of_ten(scale - self.scale), scale)
+        } else {
+            Decimal::new(div_round(&self.mantissa, &power_of_ten(self.scale - scale), mode), scale)
+        }
+    }
+
+    /// The same value without trailing zeros after the point.
+    pub fn normalize(&self) -> Decimal {
+        let mut d = self.clone();
+        let ten = BigInt::from(10);
+        while d.scale > 0 {
+            let (q, r) = d.mantissa.div_rem(&ten);
+            if !r.is_zero() {
+                break;
+            }
+            d = Decimal::new(q, d.scale - 1);
+        }
+        d
+    }
+
+    /// `self / rhs` rounded to `scale` decimal places. Panics if `rhs` is
+    /// zero.
+    pub fn div_to_scale(&self, rhs: &Decimal, scale: u32, mode: Rounding) -> Decimal {
+        assert!(!rhs.is_zero(), "division by zero");
+        // (m1 / 10^s1) / (m2 / 10^s2) = m1 · 10^s2 / (m2 · 10^s1), scaled up
+        // by 10^scale before rounding to an integer.
+        let mut n = &self.mantissa * power_of_ten(rhs.scale + scale);
+        let mut d = &rhs.mantissa * power_of_ten(self.scale);
+        if d.is_negative() {
+            n = -n;
+            d = -d;
+        }
+        Decimal::new(div_round(&n, &d, mode), scale)
+    }
+
+    /// Both operands' mantissas at their common scale.
+    fn aligned(&self, rhs: &Decimal) -> (BigInt, BigInt, u32) {
+        let scale = self.scale.max(rhs.scale);
+        (self.rescale(scale, Rounding::Truncate).mantissa,
+         rhs.rescale(scale, Rounding::Truncate).mantissa,
+         scale)
+    }
+}
+
+impl Default for Decimal {
+    fn default() -> Decimal {
+        Decimal::zero()
+    }
+}
+
+impl From<BigInt> for Decimal {
+    fn from(n: BigInt) -> Decimal {
+        Decimal::new(n, 0)
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($($t:ty)*) => {$(
+        impl From<$t> for Decimal {
+            fn from(n: $t) -> Decimal {
+                Decimal::new(BigInt::from(n), 0)
+            }
+        }
+    )*};
+}
+
+impl_from_primitive!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+impl FromStr for Decimal {
+    type Err = InputError;
+
+    /// Digits with an optional sign, decimal point and exponent, like
+    /// `-3.14159` or `1.5e-3`. The scale is the number of digits after the
+    /// point, adjusted by the exponent.
+    fn from_str(s: &str) -> Result<Decimal, InputError> {
+        let bad_exponent = |e: usize| InputError::new(&s[e + 1..], "an exponent").at(1, e + 2);
+        let (number, exp, exp_at) = match s.find(['e', 'E']) {
+            Some(e) => {
+                let exp: i64 = s[e + 1..].parse().map_err(|_| bad_exponent(e).because("invalid exponent"))?;
+                (&s[..e], exp, e)
+            },
+            None => (s, 0, 0),
+        };
+
+        let unsigned = number.trim_start_matches(['+', '-']);
+        let sign_len = number.len() - unsigned.len();
+        let (int, frac) = match unsigned.find('.') {
+            Some(point) => (&unsigned[..point], &unsigned[point + 1..]),
+            None => (unsigned, ""),
+        };
+        if sign_len > 1 || int.is_empty() && frac.is_empty() {
+            return Err(InputError::new(s, "a decimal"));
+        }
+        let frac_start = number.len() - frac.len();
+        for (start, part) in [(sign_len, int), (frac_start, frac)] {
+            if let Some(i) = part.find(|c: char| !c.is_ascii_digit()) {
+                let c = part[i..].chars().next().unwrap();
+                return Err(InputError::new(s, "a decimal").at(1, start + i + 1)
+                           .because(format!("unexpected {:?}", c)));
+            }
+        }
+
+        let mantissa: BigInt = format!("{}{}{}", &number[..sign_len], int, frac).parse()?;
+        let out_of_range = || bad_exponent(exp_at).because("exponent out of range");
+        let scale = (frac.len() as i64).checked_sub(exp).ok_or_else(out_of_range)?;
+        // A long fraction spelled out in full is fine; it's only exponents
+        // that can ask for far more digits than the input has.
+        let max_scale = (MAX_EXPONENT as usize).max(frac.len());
+        match u32::try_from(scale) {
+            Ok(scale) if scale as usize <= max_scale => Ok(Decimal::new(mantissa, scale)),
+            Ok(_) => Err(out_of_range()),
+            Err(_) => {
+                let shift = u32::try_from(-scale).ok()
+                    .filter(|&shift| shift <= MAX_EXPONENT)
+                    .ok_or_else(out_of_range)?;
+                Ok(Decimal::new(mantissa * power_of_ten(shift), 0))
+            },
+        }
+    }
+}
+
+/// Writes every digit of the scale; a precision flag, as in `{:.3}`, rounds
+/// or pads to that many places instead, rounding half to even like the float
+/// formatting it replaces.
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let d = match f.precision() {
+            Some(places) => self.rescale(places as u32, Rounding::HalfEven),
+            None => self.clone(),
+        };
+        let scale = d.scale as usize;
+        let mut digits = d.mantissa.abs().to_string();
+        if digits.len() <= scale {
+            digits.insert_str(0, &"0".repeat(scale + 1 - digits.len()));
+        }
+        let (int, frac) = digits.split_at(digits.len() - scale);
+        let s = if scale > 0 { format!("{}.{}", int, frac) } else { int.to_string() };
+        f.pad_integral(!d.is_negative(), "", &s)
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        let (a, b, _) = self.aligned(other);
+        a.cmp(&b)
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal::new(-self.mantissa, self.scale)
+    }
+}
+
+impl Neg for &Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal::new(-&self.mantissa, self.scale)
+    }
+}
+
+impl Add<&Decimal> for &Decimal {
+    type Output = Decimal;
+
+    fn add(self, rhs: &Decimal) -> Decimal {
+        let (a, b, scale) = self.aligned(rhs);
+        Decimal::new(a + b, scale)
+    }
+}
+
+impl Sub<&Decimal> for &Decimal {
+    type Output = Decimal;
+
+    fn sub(self, rhs: &Decimal) -> Decimal {
+        let (a, b, scale) = self.aligned(rhs);
+        Decimal::new(a - b, scale)
+    }
+}
+
+impl Mul<&Decimal> for &Decimal {
+    type Output = Decimal;
+
+    fn mul(self, rhs: &Decimal) -> Decimal {
+        Decimal::new(&self.mantissa * &rhs.mantissa, self.scale + rhs.scale)
+    }
+}
+
+forward_binop!(Decimal: Add, add, AddAssign, add_assign);
+forward_binop!(Decimal: Sub, sub, SubAssign, sub_assign);
+forward_binop!(Decimal: Mul, mul, MulAssign, mul_assign);
+
+#[cfg(test)]
+mod tests {
+    use super::{Decimal, Rounding};
+    use bigint::BigInt;
+    use rational::Rational;
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for s in &["3.14159", "-0.5", "0", "12", "0.000", "-12.340", "100000000000000000000.01"] {
+            assert_eq!(dec(s).to_string(), *s);
+        }
+        assert_eq!(dec("+.5").to_string(), "0.5");
+        assert_eq!(dec("7.").to_string(), "7");
+        assert_eq!(dec("1.5e-3").to_string(), "0.0015");
+        assert_eq!(dec("1.5E3").to_string(), "1500");
+        assert_eq!(dec("-25e-1").to_string(), "-2.5");
+
+        let err = "3.1x".parse::<Decimal>().unwrap_err();
+        assert_eq!(err.column, Some(4));
+        for (s, exponent) in [("1e", ""), ("1e+", "+"), ("-2.5E1x", "1x")] {
+            let err = s.parse::<Decimal>().unwrap_err();
+            assert_eq!(err.token.as_ref().map(|t| &t[..]), Some(exponent));
+            assert_eq!(err.column, Some(s.len() - exponent.len() + 1), "{}", s);
+        }
+        assert!("".parse::<Decimal>().is_err());
+        assert!("-.".parse::<Decimal>().is_err());
+        assert!("1.2.3".parse::<Decimal>().is_err());
+    }
+
+    #[test]
+    fn test_exponent_range() {
+        assert_eq!(dec("1e100000").to_string().len(), 100_001);
+        assert_eq!(dec("1e-100000"), Decimal::new(BigInt::one(), 100_000));
+        assert_eq!(dec("1.2e-99999"), Decimal::new(BigInt::from(12), 100_000));
+        let long = format!("0.{}1", "0".repeat(100_000));
+        assert_eq!(dec(&long).to_string(), long);
+        for s in &["1e100001", "1e4294967296", "1e1000000000", "1e-100001", "1.25e-99999", "1e-4294967295",
+                   "1e-4294967297", "1.5e-9223372036854775808"] {
+            let err = s.parse::<Decimal>().unwrap_err();
+            let e = s.find('e').unwrap();
+            assert_eq!((err.column, err.token.as_ref().map(|t| &t[..])), (Some(e + 2), Some(&s[e + 1..])), "{}", s);
+            assert_eq!(err.reason.as_ref().map(|r| &r[..]), Some("exponent out of range"));
+            assert!(s.parse::<Rational>().is_err(), "{}", s);
+        }
+
+        // Scales past what `{:0>width$}` can pad still print.
+        let tiny = dec("1e-100000");
+        let printed = tiny.to_string();
+        assert_eq!((printed.len(), &printed[..4], printed.ends_with("01")), (100_002, "0.00", true));
+        assert_eq!(format!("{:.*}", 65_535, dec("2.5")).len(), 65_537);
+        assert_eq!((&tiny * &tiny).to_string().len(), 200_002);
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        use super::Rounding::*;
+
+        let values = ["5.5", "2.5", "1.6", "1.1", "1.0", "-1.0", "-1.1", "-1.6", "-2.5", "-5.5"];
+        let expected: [(Rounding, [i64; 10]); 5] = [
+            (HalfUp, [6, 3, 2, 1, 1, -1, -1, -2, -3, -6]),
+            (HalfEven, [6, 2, 2, 1, 1, -1, -1, -2, -2, -6]),
+            (Floor, [5, 2, 1, 1, 1, -1, -2, -2, -3, -6]),
+            (Ceil, [6, 3, 2, 2, 1, -1, -1, -1, -2, -5]),
+            (Truncate, [5, 2, 1, 1, 1, -1, -1, -1, -2, -5]),
+        ];
+        for &(mode, ref results) in &expected {
+            for (s, &r) in values.iter().zip(results) {
+                assert_eq!(dec(s).rescale(0, mode), Decimal::from(r), "{} {:?}", s, mode);
+            }
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(dec("1.25") + dec("2.5"), dec("3.75"));
+        assert_eq!((dec("1.25") + dec("2.5")).scale(), 2);
+        assert_eq!(dec("1.25") - dec("2.5"), dec("-1.25"));
+        assert_eq!((dec("1.5") * dec("-0.25")).to_string(), "-0.375");
+        assert_eq!(-dec("0.1"), dec("-0.1"));
+
+        let mut total = Decimal::zero();
+        for _ in 0..10 {
+            total += dec("0.1");
+        }
+        assert_eq!(total, Decimal::one());
+    }
+
+    #[test]
+    fn test_div_to_scale() {
+        let third = Decimal::one().div_to_scale(&Decimal::from(3), 3, Rounding::HalfEven);
+        assert_eq!(third.to_string(), "0.333");
+        let x = dec("2").div_to_scale(&dec("-0.3"), 2, Rounding::HalfUp);
+        assert_eq!(x.to_string(), "-6.67");
+        let x = dec("2").div_to_scale(&dec("-0.3"), 2, Rounding::Ceil);
+        assert_eq!(x.to_string(), "-6.66");
+        assert_eq!(dec("10.5").div_to_scale(&dec("0.25"), 0, Rounding::Floor), Decimal::from(42));
+    }
+
+    #[test]
+    fn test_precision_flag() {
+        assert_eq!(format!("{:.2}", dec("2.675")), "2.68");
+        assert_eq!(format!("{:.2}", dec("2.665")), "2.66");
+        assert_eq!(format!("{:.3}", dec("0.5")), "0.500");
+        assert_eq!(format!("{:.0}", dec("-0.4")), "0");
+        assert_eq!(format!("{:>8.1}|{:<6}|{:+}", dec("-3.14159"), dec("1.5"), dec("2")),
+                   "    -3.1|1.5   |+2");
+        assert_eq!(format!("{:08.2}", dec("-1.5")), "-0001.50");
+    }
+
+    #[test]
+    fn test_ordering_ignores_scale() {
+        assert_eq!(dec("1.50"), dec("1.5"));
+        assert_eq!(dec("1.500").normalize().to_string(), "1.5");
+        assert_eq!(dec("100").normalize().to_string(), "100");
+        assert!(dec("-0.01") < dec("0"));
+        assert!(dec("2.1") > dec("2.09"));
+    }
+
+    #[test]
+    fn test_rational_conversions() {
+        let r = Rational::new(BigInt::from(-1), BigInt::from(6));
+        assert_eq!(Decimal::from_rational(&r, 4, Rounding::HalfEven).to_string(), "-0.1667");
+        assert_eq!(Decimal::from_rational(&r, 4, Rounding::Truncate).to_string(), "-0.1666");
+        assert_eq!(dec("-0.125").to_rational(), Rational::new(BigInt::from(-1), BigInt::from(8)));
+    }
+}
@@ -0,0 +1,204 @@
+use std::{any, convert, error, fmt, io, num, str};
+
+/// A problem with a challenge's input, located as precisely as possible.
+///
+/// Lines and columns are 1-based. Errors returned from `FromStr` impls are
+/// positioned relative to the start of the string being parsed; `Scanner`
+/// translates them into positions within the whole input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// The offending text, or `None` if there was nothing to read.
+    pub token: Option<String>,
+    pub expected: Option<String>,
+    pub reason: Option<String>,
+}
+
+impl InputError {
+    pub fn new<S: Into<String>>(token: &str, expected: S) -> InputError {
+        InputError {
+            line: None,
+            column: None,
+            token: Some(token.to_string()),
+            expected: Some(expected.into()),
+            reason: None,
+        }
+    }
+
+    pub fn end_of_input<S: Into<String>>(expected: S) -> InputError {
+        InputError {
+            line: None,
+            column: None,
+            token: None,
+            expected: Some(expected.into()),
+            reason: Some("unexpected end of input".to_string()),
+        }
+    }
+
+    fn from_reason<S: ToString>(reason: S) -> InputError {
+        InputError {
+            line: None,
+            column: None,
+            token: None,
+            expected: None,
+            reason: Some(reason.to_string()),
+        }
+    }
+
+    pub fn at(mut self, line: usize, column: usize) -> InputError {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    pub fn because<S: ToString>(mut self, reason: S) -> InputError {
+        self.reason = Some(reason.to_string());
+        self
+    }
+
+    /// Fill in the token and expected type if the error doesn't already know
+    /// something more specific.
+    pub fn or_token<T>(mut self, token: &str) -> InputError {
+        if self.token.is_none() {
+            self.token = Some(token.to_string());
+        }
+        if self.expected.is_none() {
+            self.expected = Some(type_name::<T>());
+        }
+        self
+    }
+
+    /// Translate a position relative to some text into an absolute one,
+    /// given where that text starts.
+    pub fn offset(mut self, line: usize, column: usize) -> InputError {
+        match (self.line, self.column) {
+            (Some(1), Some(c)) | (None, Some(c)) => {
+                self.line = Some(line);
+                self.column = Some(column + c - 1);
+            },
+            (Some(l), _) => self.line = Some(line + l - 1),
+            (None, None) => {
+                self.line = Some(line);
+                self.column = Some(column);
+            },
+        }
+        self
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {}, column {}: ", line, column)?,
+            (Some(line), None) => write!(f, "line {}: ", line)?,
+            _ => {},
+        }
+        if let Some(ref expected) = self.expected {
+            write!(f, "expected {}", expected)?;
+            if self.token.is_some() {
+                f.write_str(", ")?;
+            }
+        }
+        if let Some(ref token) = self.token {
+            write!(f, "found {:?}", token)?;
+        }
+        if let Some(ref reason) = self.reason {
+            if self.expected.is_some() || self.token.is_some() {
+                f.write_str(": ")?;
+            }
+            f.write_str(reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for InputError {}
+
+impl From<io::Error> for InputError {
+    fn from(err: io::Error) -> InputError {
+        InputError::from_reason(err)
+    }
+}
+
+impl From<num::ParseIntError> for InputError {
+    fn from(err: num::ParseIntError) -> InputError {
+        InputError::from_reason(err)
+    }
+}
+
+impl From<num::ParseFloatError> for InputError {
+    fn from(err: num::ParseFloatError) -> InputError {
+        InputError::from_reason(err)
+    }
+}
+
+impl From<str::ParseBoolError> for InputError {
+    fn from(err: str::ParseBoolError) -> InputError {
+        InputError::from_reason(err)
+    }
+}
+
+impl From<std::char::ParseCharError> for InputError {
+    fn from(err: std::char::ParseCharError) -> InputError {
+        InputError::from_reason(err)
+    }
+}
+
+impl From<convert::Infallible> for InputError {
+    fn from(err: convert::Infallible) -> InputError {
+        match err {}
+    }
+}
+
+/// The name of a type without its module path, e.g. `Vec<String>` rather
+/// than `alloc::vec::Vec<alloc::string::String>`.
+pub fn type_name<T>() -> String {
+    let mut name = String::new();
+    let mut path = String::new();
+    for c in any::type_name::<T>().chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            path.push(c);
+        } else {
+            name.push_str(path.rsplit("::").next().unwrap_or(""));
+            path.clear();
+            name.push(c);
+        }
+    }
+    name.push_str(path.rsplit("::").next().unwrap_or(""));
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InputError, type_name};
+
+    #[test]
+    fn test_type_name() {
+        assert_eq!(type_name::<i32>(), "i32");
+        assert_eq!(type_name::<String>(), "String");
+        assert_eq!(type_name::<Vec<Option<String>>>(), "Vec<Option<String>>");
+    }
+
+    #[test]
+    fn test_display() {
+        let err = InputError::new("abc", "i32").because("invalid digit found in string").at(3, 5);
+        assert_eq!(format!("{}", err),
+                   "line 3, column 5: expected i32, found \"abc\": invalid digit found in string");
+
+        let err = InputError::end_of_input("u64");
+        assert_eq!(format!("{}", err), "expected u64: unexpected end of input");
+    }
+
+    #[test]
+    fn test_offset() {
+        let err = InputError::new("x", "cell").at(1, 3).offset(4, 7);
+        assert_eq!((err.line, err.column), (Some(4), Some(9)));
+
+        let err = InputError::new("x", "cell").at(2, 3).offset(4, 7);
+        assert_eq!((err.line, err.column), (Some(5), Some(3)));
+
+        let err = InputError::new("x", "cell").offset(4, 7);
+        assert_eq!((err.line, err.column), (Some(4), Some(7)));
+    }
+}
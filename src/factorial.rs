@@ -0,0 +1,204 @@
+//! Exact factorials and binomial coefficients.
+//!
+//! Everything here is built from two pieces: the primes up to `n`, and a
+//! product tree that multiplies many small factors in balanced pairs so that
+//! the expensive multiplications happen between operands of similar size.
+
+use bigint::BigInt;
+use number_theory::primes_up_to;
+
+/// The product of `factors`, by binary splitting.
+pub fn product(factors: &[u64]) -> BigInt {
+    // Pack runs of factors into single words first; the tree only needs to
+    // start once the partial products outgrow a u64.
+    let mut words = vec![];
+    let mut word = 1u64;
+    for &f in factors {
+        match word.checked_mul(f) {
+            Some(w) => word = w,
+            None => {
+                words.push(word);
+                word = f;
+            },
+        }
+    }
+    words.push(word);
+    product_tree(&words)
+}
+
+fn product_tree(words: &[u64]) -> BigInt {
+    match words.len() {
+        0 => BigInt::one(),
+        1 => BigInt::from_u64(words[0]),
+        len => {
+            let (low, high) = words.split_at(len / 2);
+            product_tree(low) * product_tree(high)
+        },
+    }
+}
+
+/// The product of the integers in `low..high`.
+pub fn product_range(low: u64, high: u64) -> BigInt {
+    let factors: Vec<u64> = (low..high).collect();
+    product(&factors)
+}
+
+/// The exponent of the prime `p` in `n!`, by Legendre's formula.
+fn legendre(n: u64, p: u64) -> u64 {
+    let mut exp = 0;
+    let mut m = n / p;
+    while m > 0 {
+        exp += m;
+        m /= p;
+    }
+    exp
+}
+
+/// Multiply out `∏ p^exponent(p)` over `primes`.
+fn from_prime_exponents<F>(primes: &[u64], exponent: F) -> BigInt
+    where F: Fn(u64) -> u64
+{
+    let mut factors = vec![];
+    for &p in primes {
+        for _ in 0..exponent(p) {
+            factors.push(p);
+        }
+    }
+    product(&factors)
+}
+
+/// `n! / (⌊n/2⌋!)²`, the "swinging factorial". Every prime appears in it at
+/// most to the power that keeps it below `n`, so it is cheap to build.
+fn swing(n: u64, primes: &[u64]) -> BigInt {
+    let primes = &primes[..primes.iter().position(|&p| p > n).unwrap_or(primes.len())];
+    from_prime_exponents(primes, |p| {
+        let mut exp = 0;
+        let mut q = n / p;
+        while q > 0 {
+            exp += q & 1;
+            q /= p;
+        }
+        exp
+    })
+}
+
+/// `n!`, by Luschny's prime-swing method: `n! = (⌊n/2⌋!)² · swing(n)`.
+pub fn factorial(n: u64) -> BigInt {
+    fn go(n: u64, primes: &[u64]) -> BigInt {
+        if n < 2 {
+            return BigInt::one();
+        }
+        let half = go(n / 2, primes);
+        &half * &half * swing(n, primes)
+    }
+    go(n, &primes_up_to(n))
+}
+
+/// `n!! = n · (n - 2) · (n - 4) · …`, down to 1 or 2.
+pub fn double_factorial(n: u64) -> BigInt {
+    if n.is_multiple_of(2) {
+        // (2m)!! = 2^m · m!
+        factorial(n / 2) << (n / 2)
+    } else {
+        let odd: Vec<u64> = (1..n + 1).step_by(2).collect();
+        product(&odd)
+    }
+}
+
+/// The binomial coefficient `n choose k`, zero if `k > n`.
+pub fn binomial(n: u64, k: u64) -> BigInt {
+    if k > n {
+        return BigInt::zero();
+    }
+    multinomial(&[k, n - k])
+}
+
+/// The multinomial coefficient `(k1 + k2 + …)! / (k1! · k2! · …)`: the
+/// number of ways to split that many items into groups of those sizes.
+pub fn multinomial(ks: &[u64]) -> BigInt {
+    let n = ks.iter().sum();
+    from_prime_exponents(&primes_up_to(n), |p| {
+        legendre(n, p) - ks.iter().map(|&k| legendre(k, p)).sum::<u64>()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{binomial, double_factorial, factorial, multinomial, product_range};
+    use bigint::BigInt;
+    use test::Bencher;
+
+    #[test]
+    fn test_small_factorials() {
+        let mut expected = BigInt::one();
+        for n in 0..200u64 {
+            if n > 0 {
+                expected *= n;
+            }
+            assert_eq!(factorial(n), expected, "{}!", n);
+            assert_eq!(product_range(1, n + 1), expected);
+        }
+        assert_eq!(factorial(25), "15511210043330985984000000".parse::<BigInt>().unwrap());
+    }
+
+    #[test]
+    fn test_large_factorial() {
+        let n = 100000;
+        let f = factorial(n);
+        let p = 1_000_000_007;
+        let expected = (1..n + 1).fold(1, |acc, m| acc * m % p);
+        assert_eq!(f.clone() % BigInt::from_u64(p), BigInt::from_u64(expected));
+        assert_eq!(f, product_range(1, n + 1));
+    }
+
+    #[test]
+    fn test_double_factorial() {
+        assert_eq!(double_factorial(0), BigInt::one());
+        assert_eq!(double_factorial(1), BigInt::one());
+        assert_eq!(double_factorial(8), BigInt::from_u64(384));
+        assert_eq!(double_factorial(9), BigInt::from_u64(945));
+        for n in 2..60 {
+            assert_eq!(double_factorial(n) * double_factorial(n - 1), factorial(n));
+        }
+    }
+
+    #[test]
+    fn test_binomial() {
+        assert_eq!(binomial(5, 2), BigInt::from_u64(10));
+        assert_eq!(binomial(5, 6), BigInt::zero());
+        assert_eq!(binomial(0, 0), BigInt::one());
+        assert_eq!(binomial(100, 50), "100891344545564193334812497256".parse::<BigInt>().unwrap());
+
+        // Pascal's rule.
+        for n in 1..40 {
+            for k in 1..n {
+                assert_eq!(binomial(n, k), binomial(n - 1, k - 1) + binomial(n - 1, k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_binomial() {
+        let half = factorial(50000);
+        assert_eq!(binomial(100000, 50000) * &half * &half, factorial(100000));
+    }
+
+    #[test]
+    fn test_multinomial() {
+        assert_eq!(multinomial(&[]), BigInt::one());
+        assert_eq!(multinomial(&[2, 1, 1]), BigInt::from_u64(12));
+        // MISSISSIPPI: 1 M, 4 I, 4 S, 2 P.
+        assert_eq!(multinomial(&[1, 4, 4, 2]), BigInt::from_u64(34650));
+        assert_eq!(multinomial(&[7, 0, 3]), binomial(10, 3));
+    }
+
+    #[bench]
+    fn bench_factorial_prime_swing(bench: &mut Bencher) {
+        bench.iter(|| factorial(20000));
+    }
+
+    #[bench]
+    fn bench_factorial_product_tree(bench: &mut Bencher) {
+        bench.iter(|| product_range(1, 20001));
+    }
+}
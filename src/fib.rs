@@ -0,0 +1,256 @@
+//! Fibonacci and Lucas numbers by fast doubling, exactly or modulo anything.
+//!
+//! Fast doubling gets from `(F(k), F(k+1))` to `(F(2k), F(2k+1))` with
+//! three multiplications, using
+//!
+//! ```text
+//! F(2k)     = F(k) (2 F(k+1) - F(k))
+//! F(2k + 1) = F(k)² + F(k+1)²
+//! ```
+//!
+//! so `F(n)` takes one doubling per bit of `n`.
+
+use std::convert::TryFrom;
+
+use bigint::BigInt;
+use modint::{add_mod, mul_mod, sub_mod};
+use number_theory::{factorize, lcm};
+use ring::Ring;
+
+/// `(F(n), F(n+1))`, in any ring: `i64`, `ModInt` or `BigInt`.
+pub fn fib_pair<T: Ring>(n: u64) -> (T, T) {
+    let (mut a, mut b) = (T::zero(), T::one());
+    for i in (0..64 - n.leading_zeros()).rev() {
+        let even = a.clone() * (b.clone() + b.clone() - a.clone());
+        let odd = a.clone() * a + b.clone() * b;
+        if (n >> i) & 1 == 1 {
+            a = odd.clone();
+            b = even + odd;
+        } else {
+            a = even;
+            b = odd;
+        }
+    }
+    (a, b)
+}
+
+/// `F(n)`, where `F(0) = 0` and `F(1) = 1`.
+///
+/// Takes the last doubling by hand rather than through `fib_pair`, which
+/// would also work out `F(n+1)`: in a fixed-width type, that can overflow
+/// when `F(n)` itself fits.
+pub fn fib<T: Ring>(n: u64) -> T {
+    let (a, b) = fib_pair::<T>(n / 2);
+    if n.is_multiple_of(2) {
+        a.clone() * (b.clone() + b - a)
+    } else {
+        a.clone() * a + b.clone() * b
+    }
+}
+
+/// `L(n)`, where `L(0) = 2` and `L(1) = 1`.
+pub fn lucas<T: Ring>(n: u64) -> T {
+    // L(n) = F(n+1) + F(n-1), which never passes through anything bigger.
+    let (a, b) = fib_pair::<T>(n);
+    b.clone() + (b - a)
+}
+
+/// `F(n)` as a `u64`, or `None` past `F(93)`, the largest that fits.
+pub fn fib_u64(n: u64) -> Option<u64> {
+    if n > 93 {
+        return None;
+    }
+    Some(fib::<i128>(n) as u64)
+}
+
+/// `L(n)` as a `u64`, or `None` past `L(92)`, the largest that fits.
+pub fn lucas_u64(n: u64) -> Option<u64> {
+    if n > 92 {
+        return None;
+    }
+    Some(lucas::<i128>(n) as u64)
+}
+
+/// `(F(n) mod m, F(n+1) mod m)` for a modulus only known at run time.
+fn fib_pair_mod(n: u64, m: u64) -> (u64, u64) {
+    let (mut a, mut b) = (0, 1 % m);
+    for i in (0..64 - n.leading_zeros()).rev() {
+        let even = mul_mod(a, sub_mod(add_mod(b, b, m), a, m), m);
+        let odd = add_mod(mul_mod(a, a, m), mul_mod(b, b, m), m);
+        if (n >> i) & 1 == 1 {
+            a = odd;
+            b = add_mod(even, odd, m);
+        } else {
+            a = even;
+            b = odd;
+        }
+    }
+    (a, b)
+}
+
+/// `F(n) mod m` for any `n`, however large or negative.
+///
+/// Fibonacci numbers modulo `m` repeat with the Pisano period, so `n` only
+/// matters modulo that; this is what makes `n` with thousands of digits
+/// cheap. An `n` that fits in a `u64` is cheaper still without it, so the
+/// period is only worked out for bigger ones, and then `m` must be in the
+/// range `pisano` supports.
+pub fn fib_mod(n: &BigInt, m: u64) -> u64 {
+    assert!(m > 0, "modulus must be positive");
+    let small = u64::try_from(&n.abs()).ok();
+    let (k, negative) = match small {
+        Some(k) => (k, n.is_negative()),
+        None => {
+            let period = BigInt::from(pisano(m));
+            let mut r = n % &period;
+            if r.is_negative() {
+                r += period;
+            }
+            (u64::try_from(&r).unwrap(), false)
+        },
+    };
+    // F(-k) = (-1)^(k+1) F(k).
+    let f = fib_pair_mod(k, m).0;
+    if negative && k.is_multiple_of(2) { sub_mod(0, f, m) } else { f }
+}
+
+/// The Pisano period: the length of the cycle of Fibonacci numbers
+/// modulo `m`. Panics if `m` is zero or above `u64::MAX / 6`; the period is
+/// at most `6m`, so that's as far as it's sure to fit.
+///
+/// Combines the periods of the prime powers dividing `m`, taking
+/// `π(p^k) = p^(k-1) π(p)`. That's unproven, but a counterexample would be
+/// a Wall–Sun–Sun prime, and none is known.
+pub fn pisano(m: u64) -> u64 {
+    assert!(m > 0, "modulus must be positive");
+    assert!(m <= u64::MAX / 6, "the Pisano period of {} may not fit in a u64", m);
+    factorize(m).into_iter().fold(1, |period, (p, k)| lcm(period, p.pow(k - 1) * pisano_prime(p)))
+}
+
+/// `π(p)` for a prime `p`.
+fn pisano_prime(p: u64) -> u64 {
+    // π(p) divides p - 1 when p is ±1 mod 10, and 2(p + 1) when it's ±3.
+    let multiple = match p {
+        2 => return 3,
+        5 => return 20,
+        _ if p % 10 == 1 || p % 10 == 9 => p - 1,
+        _ => 2 * (p + 1),
+    };
+    let mut period = multiple;
+    for (q, _) in factorize(multiple) {
+        while period.is_multiple_of(q) && fib_pair_mod(period / q, p) == (0, 1) {
+            period /= q;
+        }
+    }
+    period
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::{fib, fib_mod, fib_pair, fib_pair_mod, fib_u64, lucas, lucas_u64, pisano};
+    use bigint::BigInt;
+    use modint::{MOD_1E9_7, ModInt};
+    use recurrence::LinearRecurrence;
+    use test::Bencher;
+
+    #[test]
+    fn test_small() {
+        let (mut a, mut b) = (0u128, 1u128);
+        for n in 0..100 {
+            assert_eq!(fib_u64(n), u64::try_from(a).ok(), "F({})", n);
+            assert_eq!(lucas_u64(n), u64::try_from(2 * b - a).ok(), "L({})", n);
+            if n <= 92 {
+                assert_eq!(fib::<i64>(n), a as i64, "F({}) as i64", n);
+            }
+            if n <= 90 {
+                assert_eq!(lucas::<i64>(n), (2 * b - a) as i64, "L({}) as i64", n);
+            }
+            (a, b) = (b, a + b);
+        }
+    }
+
+    #[test]
+    fn test_rings_agree() {
+        type Mod = ModInt<MOD_1E9_7>;
+        let recurrence = LinearRecurrence::new(vec![BigInt::one(); 2], vec![BigInt::zero(), BigInt::one()]).unwrap();
+        let modulus = BigInt::from(MOD_1E9_7);
+        for n in [0, 1, 2, 50, 93, 94, 500, 1000, 4321] {
+            let exact: BigInt = fib(n);
+            assert_eq!(exact, recurrence.nth(n), "F({})", n);
+            assert_eq!(BigInt::from(fib::<Mod>(n).value()), &exact % &modulus);
+            assert_eq!(fib_mod(&BigInt::from(n), MOD_1E9_7), fib::<Mod>(n).value());
+            // F(2n) = F(n) L(n).
+            assert_eq!(fib::<BigInt>(2 * n), exact * lucas::<BigInt>(n));
+        }
+        assert_eq!(fib::<BigInt>(300).to_string(), "222232244629420445529739893461909967206666939096499764990979600");
+    }
+
+    #[test]
+    fn test_pisano() {
+        for m in 1..300 {
+            let (mut a, mut b, mut period) = (1 % m, 1 % m, 1);
+            while (a, b) != (0, 1 % m) {
+                (a, b) = (b, (a + b) % m);
+                period += 1;
+            }
+            assert_eq!(pisano(m), period, "π({})", m);
+        }
+        assert_eq!(pisano(10), 60);
+        assert_eq!(pisano(1_000_000_000), 1_500_000_000);
+        let p = pisano(MOD_1E9_7);
+        assert_eq!(fib_pair_mod(p, MOD_1E9_7), (0, 1));
+        assert!((2 * (MOD_1E9_7 + 1)).is_multiple_of(p));
+
+        // A prime that's 7 mod 10 near the top of the range, so its period
+        // divides 2(p + 1).
+        let big = 3_074_457_345_618_258_487;
+        assert_eq!(fib_pair_mod(pisano(big), big), (0, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "may not fit in a u64")]
+    fn test_pisano_out_of_range() {
+        pisano(u64::MAX - 58);
+    }
+
+    #[test]
+    fn test_fib_mod_huge_and_negative() {
+        // F(2^k) by doubling alone, no periods involved.
+        for m in [2, 10, 1000, 999_983, MOD_1E9_7] {
+            let (mut a, mut b) = (1 % m, 1 % m);
+            for k in 1..200 {
+                let even = a * ((2 * b + m - a) % m) % m;
+                let odd = (a * a + b * b) % m;
+                (a, b) = (even, odd);
+                assert_eq!(fib_mod(&(BigInt::one() << k), m), a, "F(2^{}) mod {}", k, m);
+            }
+        }
+
+        // F(-n) = (-1)^(n+1) F(n).
+        assert_eq!(fib_mod(&BigInt::from(-1), 7), 1);
+        assert_eq!(fib_mod(&BigInt::from(-2), 7), 6);
+        assert_eq!(fib_mod(&BigInt::from(-10), 1000), 1000 - 55);
+        assert_eq!(fib_mod(&BigInt::from(12345), 1), 0);
+        assert_eq!(fib_mod(&BigInt::from(-12345), 1), 0);
+        assert_eq!(fib_mod(&-(BigInt::one() << 70), 1000), fib_mod(&(BigInt::one() << 70), 1000) * 999 % 1000);
+
+        // Small n never needs the period, so any modulus works.
+        assert_eq!(fib_mod(&BigInt::from(90), u64::MAX), fib_u64(90).unwrap());
+        assert_eq!(fib_mod(&BigInt::from(-90), u64::MAX), u64::MAX - fib_u64(90).unwrap());
+        assert_eq!(fib_pair::<ModInt<7>>(0), (ModInt::new(0), ModInt::new(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus must be positive")]
+    fn test_fib_mod_zero_modulus() {
+        fib_mod(&BigInt::from(5), 0);
+    }
+
+    #[bench]
+    fn bench_fib_mod_100000_digits(b: &mut Bencher) {
+        let n = BigInt::from(10).pow(100_000) - BigInt::one();
+        b.iter(|| fib_mod(&n, MOD_1E9_7));
+    }
+}
@@ -0,0 +1,149 @@
+//! Support for golden-file tests: sample inputs and expected outputs stored
+//! as `tests/<challenge>/inputNN.txt` and `tests/<challenge>/outputNN.txt`.
+
+use std::{fs, io};
+use std::path::{Path, PathBuf};
+
+/// A pair of fixture files for one test case.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Fixture {
+    pub name: String,
+    pub input: PathBuf,
+    pub output: PathBuf,
+}
+
+/// The names of all `[[bin]]` targets in a Cargo manifest.
+pub fn bin_names(manifest: &str) -> Vec<String> {
+    let mut names = vec![];
+    let mut in_bin = false;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_bin = line == "[[bin]]";
+        } else if in_bin {
+            let mut parts = line.splitn(2, '=').map(str::trim);
+            if let (Some("name"), Some(value)) = (parts.next(), parts.next()) {
+                names.push(value.trim_matches('"').to_string());
+            }
+        }
+    }
+    names
+}
+
+/// All fixtures in `dir`, sorted by name. Inputs without a matching output
+/// are skipped, as are empty inputs, which are placeholders that haven't been
+/// filled in yet. A missing directory has no fixtures.
+pub fn fixtures(dir: &Path) -> io::Result<Vec<Fixture>> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+    let mut found = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let file_name = match path.file_name().and_then(|n| n.to_str()) {
+            Some(file_name) => file_name.to_string(),
+            None => continue,
+        };
+        if file_name.starts_with("input") && file_name.ends_with(".txt") {
+            let name = file_name["input".len()..file_name.len() - ".txt".len()].to_string();
+            let output = dir.join(format!("output{}.txt", name));
+            if output.is_file() && fs::metadata(&path)?.len() > 0 {
+                found.push(Fixture { name, input: path, output });
+            }
+        }
+    }
+    found.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(found)
+}
+
+/// Compare program output against the expected output line by line, the way
+/// HackerRank does: trailing whitespace on each line and trailing blank lines
+/// are ignored. Returns a report of every differing line, or `None` if the
+/// outputs match.
+pub fn diff(expected: &str, actual: &str) -> Option<String> {
+    fn lines(s: &str) -> Vec<&str> {
+        let mut lines: Vec<&str> = s.lines().map(str::trim_end).collect();
+        while lines.last() == Some(&"") {
+            lines.pop();
+        }
+        lines
+    }
+
+    let expected = lines(expected);
+    let actual = lines(actual);
+    let mut report = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        let line = match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => continue,
+            (Some(e), Some(a)) => format!("expected {:?}, found {:?}", e, a),
+            (Some(e), None) => format!("expected {:?}, found end of output", e),
+            (None, Some(a)) => format!("expected end of output, found {:?}", a),
+            (None, None) => unreachable!(),
+        };
+        report.push_str(&format!("line {}: {}\n", i + 1, line));
+    }
+
+    if report.is_empty() { None } else { Some(report) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{bin_names, diff, fixtures};
+
+    #[test]
+    fn test_bin_names() {
+        let manifest = "\
+            [package]\n\
+            name = \"hackerrank\"\n\
+            \n\
+            [[bin]]\n\
+            name = \"solve-me-first\"\n\
+            path = \"src/algorithms/warmup/solve-me-first.rs\"\n\
+            \n\
+            [features]\n\
+            name = \"not-a-bin\"\n\
+            \n\
+            [[bin]]\n\
+            path = \"src/ai/bot-building/saveprincess.rs\"\n\
+            name = \"saveprincess\"\n\
+        ";
+        assert_eq!(bin_names(manifest), vec!["solve-me-first", "saveprincess"]);
+    }
+
+    #[test]
+    fn test_fixtures() {
+        let dir = env::temp_dir().join(format!("golden-fixtures-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for &(file, contents) in &[("input01.txt", "1\n"), ("output01.txt", "1\n"),
+                                   ("input00.txt", "0\n"), ("output00.txt", "0\n"),
+                                   ("input02.txt", "2\n"),
+                                   ("input03.txt", ""), ("output03.txt", "")] {
+            fs::write(dir.join(file), contents).unwrap();
+        }
+
+        let names: Vec<String> = fixtures(&dir).unwrap().into_iter().map(|f| f.name).collect();
+        assert_eq!(names, vec!["00", "01"]);
+        assert_eq!(fixtures(&dir.join("missing")).unwrap(), vec![]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_diff_matching() {
+        assert_eq!(diff("1\n2\n", "1  \n2"), None);
+        assert_eq!(diff("1\n2\n", "1\n2\n\n\n"), None);
+    }
+
+    #[test]
+    fn test_diff_report() {
+        let report = diff("0.500\n0.333\n0.167\n", "0.500\n0.334\n").unwrap();
+        assert_eq!(report, "\
+            line 2: expected \"0.333\", found \"0.334\"\n\
+            line 3: expected \"0.167\", found end of output\n\
+        ");
+
+        let report = diff("5\n", "5\n10\n").unwrap();
+        assert_eq!(report, "line 2: expected end of output, found \"10\"\n");
+    }
+}
@@ -0,0 +1,438 @@
+//! Rectangular boards for the bot-building challenges, and routes across
+//! them.
+
+use std::{fmt, iter, ops, str};
+use std::convert::TryFrom;
+
+use error::InputError;
+
+mod pathfinding;
+
+pub use self::pathfinding::{Connectivity, Heuristic};
+
+/// A position on a board: `x` counts columns from the left, `y` rows from
+/// the top.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Coord {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Coord {
+    pub fn new(x: i32, y: i32) -> Coord {
+        Coord { x, y }
+    }
+
+    /// The neighbouring position in direction `dir`.
+    pub fn step(self, dir: Direction) -> Coord {
+        self + dir.delta()
+    }
+}
+
+impl ops::Add<Coord> for Coord {
+    type Output = Coord;
+    fn add(self, rhs: Coord) -> Coord {
+        Coord::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl ops::Sub<Coord> for Coord {
+    type Output = Coord;
+    fn sub(self, rhs: Coord) -> Coord {
+        Coord::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+/// A move to a neighbouring cell. The diagonals only come up with
+/// `Connectivity::Eight`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+    UpRight,
+    DownRight,
+    DownLeft,
+    UpLeft,
+}
+
+impl Direction {
+    /// The change in position, with `y` growing downwards.
+    pub fn delta(self) -> Coord {
+        match self {
+            Direction::Up => Coord::new(0, -1),
+            Direction::Right => Coord::new(1, 0),
+            Direction::Down => Coord::new(0, 1),
+            Direction::Left => Coord::new(-1, 0),
+            Direction::UpRight => Coord::new(1, -1),
+            Direction::DownRight => Coord::new(1, 1),
+            Direction::DownLeft => Coord::new(-1, 1),
+            Direction::UpLeft => Coord::new(-1, -1),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::UpRight => Direction::DownLeft,
+            Direction::DownRight => Direction::UpLeft,
+            Direction::DownLeft => Direction::UpRight,
+            Direction::UpLeft => Direction::DownRight,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.write_str(match *self {
+            Direction::Up => "UP",
+            Direction::Right => "RIGHT",
+            Direction::Down => "DOWN",
+            Direction::Left => "LEFT",
+            Direction::UpRight => "UP-RIGHT",
+            Direction::DownRight => "DOWN-RIGHT",
+            Direction::DownLeft => "DOWN-LEFT",
+            Direction::UpLeft => "UP-LEFT",
+        })
+    }
+}
+
+/// What's on a square, across the bot-building challenges. Each is written
+/// as a single character, given next to it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Cell {
+    /// `-`
+    Empty,
+    /// `p`
+    Princess,
+    /// `m`, the bot being played; BotClean writes it `b`.
+    Bot,
+    /// `#`
+    Wall,
+    /// `d`, waiting for the bot to clean it.
+    Dirty,
+    /// `o`, out of the bot's sight.
+    Fog,
+    /// `e`, another player's bot.
+    Opponent,
+    /// `0` to `9`, e.g. a score or a cost to enter.
+    Number(u8),
+}
+
+impl Cell {
+    pub fn symbol(self) -> char {
+        match self {
+            Cell::Empty => '-',
+            Cell::Princess => 'p',
+            Cell::Bot => 'm',
+            Cell::Wall => '#',
+            Cell::Dirty => 'd',
+            Cell::Fog => 'o',
+            Cell::Opponent => 'e',
+            Cell::Number(n) => (b'0' + n) as char,
+        }
+    }
+}
+
+impl TryFrom<char> for Cell {
+    type Error = InputError;
+
+    fn try_from(c: char) -> Result<Cell, InputError> {
+        Ok(match c {
+            '-' => Cell::Empty,
+            'p' => Cell::Princess,
+            'm' | 'b' => Cell::Bot,
+            '#' => Cell::Wall,
+            'd' => Cell::Dirty,
+            'o' => Cell::Fog,
+            'e' => Cell::Opponent,
+            '0'..='9' => Cell::Number(c as u8 - b'0'),
+            _ => return Err(InputError::new(&c.to_string(), "a cell (one of \"-pmb#doe\" or a digit)")),
+        })
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", self.symbol())
+    }
+}
+
+/// A grid of cells, indexed by `Coord`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Board {
+    width: usize,
+    height: usize,
+    cells: Vec<Vec<Cell>>,
+}
+
+impl Board {
+    /// A board of empty cells.
+    pub fn new(width: usize, height: usize) -> Board {
+        let cells = vec![vec![Cell::Empty; width]; height];
+        Board { width, height, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, c: Coord) -> bool {
+        c.x >= 0 && c.y >= 0 && (c.x as usize) < self.width && (c.y as usize) < self.height
+    }
+
+    /// The cell at `c`, or `None` off the board.
+    pub fn get(&self, c: Coord) -> Option<Cell> {
+        if self.contains(c) { Some(self[c]) } else { None }
+    }
+
+    pub fn iter(&self) -> BoardIterator<'_> {
+        BoardIterator::new(self)
+    }
+
+    /// The first cell matching `predicate`, reading row by row.
+    pub fn position<P>(&self, predicate: P) -> Option<Coord>
+        where P: Fn(Cell) -> bool
+    {
+        for (idx, cell) in self {
+            if predicate(cell) {
+                return Some(idx);
+            }
+        }
+        None
+    }
+
+    /// Every cell matching `predicate`, reading row by row.
+    pub fn positions<P>(&self, predicate: P) -> Vec<Coord>
+        where P: Fn(Cell) -> bool
+    {
+        self.iter().filter(|&(_, cell)| predicate(cell)).map(|(idx, _)| idx).collect()
+    }
+
+    /// A shortest route on an open board: all the horizontal moves, then
+    /// all the vertical ones. It goes straight through walls; `bfs` and
+    /// friends go around them.
+    pub fn path(&self, from: Coord, to: Coord) -> Vec<Direction> {
+        let delta = to - from;
+        let horiz = if delta.x < 0 { Direction::Left } else { Direction::Right };
+        let vert = if delta.y < 0 { Direction::Up } else { Direction::Down };
+
+        let mut path = vec![];
+        for _ in 0..delta.x.abs() {
+            path.push(horiz);
+        }
+        for _ in 0..delta.y.abs() {
+            path.push(vert);
+        }
+
+        path
+    }
+}
+
+impl ops::Index<Coord> for Board {
+    type Output = Cell;
+
+    fn index(&self, c: Coord) -> &Cell {
+        self.cells.index(c.y as usize).index(c.x as usize)
+    }
+}
+
+impl ops::IndexMut<Coord> for Board {
+    fn index_mut(&mut self, c: Coord) -> &mut Cell {
+        self.cells.index_mut(c.y as usize).index_mut(c.x as usize)
+    }
+}
+
+impl str::FromStr for Board {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> Result<Board, InputError> {
+        let lines: Vec<(usize, &str)> = s.split('\n')
+            .enumerate()
+            .filter(|&(_, l)| !l.trim().is_empty())
+            .collect();
+        if lines.is_empty() {
+            return Err(InputError::new(s, "a board"));
+        }
+        let width = lines[0].1.chars().count();
+        let height = lines.len();
+
+        let mut board = Board::new(width, height);
+
+        for (y, &(line_number, l)) in lines.iter().enumerate() {
+            if l.chars().count() != width {
+                return Err(InputError::new(l, format!("a row of {} cells", width))
+                           .at(line_number + 1, 1));
+            }
+            for (x, c) in l.chars().enumerate() {
+                board[Coord::new(x as i32, y as i32)] = Cell::try_from(c)
+                    .map_err(|err| err.at(line_number + 1, x + 1))?;
+            }
+        }
+
+        Ok(board)
+    }
+}
+
+impl fmt::Display for Board {
+    /// One line per row, in the same alphabet `from_str` reads.
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        for (y, row) in self.cells.iter().enumerate() {
+            if y > 0 {
+                fmt.write_str("\n")?;
+            }
+            for cell in row {
+                write!(fmt, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> iter::IntoIterator for &'a Board {
+    type Item = (Coord, Cell);
+    type IntoIter = BoardIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Every cell with its position, row by row.
+pub struct BoardIterator<'a> {
+    board: &'a Board,
+    x: i32,
+    y: i32,
+    done: bool,
+}
+
+impl<'a> BoardIterator<'a> {
+    fn new(board: &'a Board) -> BoardIterator<'a> {
+        let done = board.width == 0 || board.height == 0;
+        BoardIterator { board, x: 0, y: 0, done }
+    }
+}
+
+impl Iterator for BoardIterator<'_> {
+    type Item = (Coord, Cell);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            None
+        } else {
+            let idx = Coord::new(self.x, self.y);
+            let res = (idx, self.board[idx]);
+            self.x += 1;
+            if self.x as usize >= self.board.width {
+                self.x = 0;
+                self.y += 1;
+            }
+            if self.y as usize >= self.board.height {
+                self.done = true;
+            }
+            Some(res)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Board, Cell, Coord, Direction};
+
+    #[test]
+    fn test_indexing() {
+        let mut board = Board::new(3, 3);
+        assert_eq!(board[Coord::new(1, 2)], Cell::Empty);
+        board[Coord::new(1, 2)] = Cell::Princess;
+        assert_eq!(board[Coord::new(1, 2)], Cell::Princess);
+        assert_eq!(board.get(Coord::new(1, 2)), Some(Cell::Princess));
+        assert_eq!(board.get(Coord::new(3, 0)), None);
+        assert_eq!(board.get(Coord::new(0, -1)), None);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let input = "\
+            p--\n\
+            -m#\n\
+            ---\n\
+        ";
+        let actual: Board = input.parse().unwrap();
+        let mut expected = Board::new(3, 3);
+        expected[Coord::new(0, 0)] = Cell::Princess;
+        expected[Coord::new(1, 1)] = Cell::Bot;
+        expected[Coord::new(2, 1)] = Cell::Wall;
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_position() {
+        let input = [
+            "---",
+            "--m",
+            "p--",
+        ].join("\n");
+        let board: Board = input.parse().unwrap();
+
+        assert_eq!(board[Coord::new(2, 1)], Cell::Bot);
+        assert_eq!(board.position(|c| c == Cell::Princess), Some(Coord::new(0, 2)));
+        assert_eq!(board.position(|c| c == Cell::Wall), None);
+        assert_eq!(board.iter().count(), 9);
+    }
+
+    #[test]
+    fn test_from_str_unknown_cell() {
+        let err = "p--\n-x-\n---".parse::<Board>().unwrap_err();
+        assert_eq!(err.token, Some("x".to_string()));
+        assert_eq!((err.line, err.column), (Some(2), Some(2)));
+
+        assert!("p--\n-m\n---".parse::<Board>().is_err());
+        assert!("p--\n-m-\n--P".parse::<Board>().is_err());
+    }
+
+    #[test]
+    fn test_full_alphabet() {
+        let input = "\
+            -d#o\n\
+            m0e9\n\
+            pd-d\
+        ";
+        let board: Board = input.parse().unwrap();
+        assert_eq!(board[Coord::new(1, 0)], Cell::Dirty);
+        assert_eq!(board[Coord::new(3, 0)], Cell::Fog);
+        assert_eq!(board[Coord::new(1, 1)], Cell::Number(0));
+        assert_eq!(board[Coord::new(2, 1)], Cell::Opponent);
+        assert_eq!(board[Coord::new(3, 1)], Cell::Number(9));
+        assert_eq!(board.to_string(), input);
+        assert_eq!("b-\nd-".parse::<Board>().unwrap().to_string(), "m-\nd-");
+
+        assert_eq!(board.positions(|c| c == Cell::Dirty),
+                   vec![Coord::new(1, 0), Coord::new(1, 2), Coord::new(3, 2)]);
+        assert_eq!(board.positions(|c| matches!(c, Cell::Number(_))).len(), 2);
+        assert_eq!(board.positions(|c| c == Cell::Princess), vec![Coord::new(0, 2)]);
+        assert!(Board::new(2, 2).positions(|c| c == Cell::Wall).is_empty());
+    }
+
+    #[test]
+    fn test_directions() {
+        let all = [
+            Direction::Up, Direction::Right, Direction::Down, Direction::Left,
+            Direction::UpRight, Direction::DownRight, Direction::DownLeft, Direction::UpLeft,
+        ];
+        let origin = Coord::new(4, 7);
+        for &dir in &all {
+            assert_eq!(origin.step(dir).step(dir.opposite()), origin);
+        }
+        assert_eq!(origin.step(Direction::UpRight), Coord::new(5, 6));
+        assert_eq!(Direction::DownLeft.to_string(), "DOWN-LEFT");
+    }
+}
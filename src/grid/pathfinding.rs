@@ -0,0 +1,310 @@
+//! Shortest routes around obstacles: breadth-first search when every step
+//! costs the same, Dijkstra and A* when cells have weights.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use super::{Board, Cell, Coord, Direction};
+
+/// Which neighbours a single move can reach.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Connectivity {
+    /// Up, down, left and right.
+    Four,
+    /// The diagonals too, each a single move.
+    Eight,
+}
+
+impl Connectivity {
+    /// The moves allowed, straight ones first.
+    pub fn directions(self) -> &'static [Direction] {
+        const ALL: [Direction; 8] = [
+            Direction::Up, Direction::Right, Direction::Down, Direction::Left,
+            Direction::UpRight, Direction::DownRight, Direction::DownLeft, Direction::UpLeft,
+        ];
+        match self {
+            Connectivity::Four => &ALL[..4],
+            Connectivity::Eight => &ALL,
+        }
+    }
+}
+
+/// A lower bound on the number of moves left, to steer A*.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Heuristic {
+    /// `|dx| + |dy|`: exact on an open board with `Connectivity::Four`.
+    Manhattan,
+    /// `max(|dx|, |dy|)`: exact on an open board with `Connectivity::Eight`.
+    Chebyshev,
+}
+
+impl Heuristic {
+    pub fn estimate(self, from: Coord, to: Coord) -> u64 {
+        let (dx, dy) = ((to.x - from.x).unsigned_abs(), (to.y - from.y).unsigned_abs());
+        match self {
+            Heuristic::Manhattan => (dx + dy) as u64,
+            Heuristic::Chebyshev => dx.max(dy) as u64,
+        }
+    }
+}
+
+impl Board {
+    /// A route from `from` to `to` with the fewest moves, only entering
+    /// cells that are `passable`, or `None` if there isn't one. The starting
+    /// cell itself is never checked.
+    pub fn bfs<P>(&self, from: Coord, to: Coord, connectivity: Connectivity, passable: P)
+        -> Option<Vec<Direction>>
+        where P: Fn(Cell) -> bool
+    {
+        if !self.contains(from) || !self.contains(to) {
+            return None;
+        }
+        let mut came_by = vec![None; self.width * self.height];
+        let mut queue = VecDeque::new();
+        queue.push_back(from);
+        while let Some(c) = queue.pop_front() {
+            if c == to {
+                return Some(self.trace(&came_by, from, to));
+            }
+            for &dir in connectivity.directions() {
+                let next = c.step(dir);
+                match self.get(next) {
+                    Some(cell) if next != from && passable(cell) => {},
+                    _ => continue,
+                }
+                let seen = &mut came_by[self.offset(next)];
+                if seen.is_none() {
+                    *seen = Some(dir);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// The cheapest route from `from` to `to`, by Dijkstra's algorithm.
+    ///
+    /// Each move costs whatever `cost` says about the cell it enters, given
+    /// its position and contents, with `None` for cells that can't be
+    /// entered at all.
+    pub fn dijkstra<F>(&self, from: Coord, to: Coord, connectivity: Connectivity, cost: F)
+        -> Option<Vec<Direction>>
+        where F: Fn(Coord, Cell) -> Option<u64>
+    {
+        self.best_first(from, to, connectivity, |_| 0, cost)
+    }
+
+    /// The cheapest route from `from` to `to`, by A*: Dijkstra, but trying
+    /// the cells that look closer to `to` first.
+    ///
+    /// The route is only guaranteed cheapest if `heuristic` never
+    /// overestimates, which holds when every move costs at least 1 and
+    /// `Manhattan` is only used with `Connectivity::Four`.
+    pub fn astar<F>(&self, from: Coord, to: Coord, connectivity: Connectivity, heuristic: Heuristic, cost: F)
+        -> Option<Vec<Direction>>
+        where F: Fn(Coord, Cell) -> Option<u64>
+    {
+        self.best_first(from, to, connectivity, |c| heuristic.estimate(c, to), cost)
+    }
+
+    fn best_first<H, F>(&self, from: Coord, to: Coord, connectivity: Connectivity, estimate: H, cost: F)
+        -> Option<Vec<Direction>>
+        where H: Fn(Coord) -> u64,
+              F: Fn(Coord, Cell) -> Option<u64>
+    {
+        if !self.contains(from) || !self.contains(to) {
+            return None;
+        }
+        let mut spent = vec![u64::MAX; self.width * self.height];
+        let mut came_by = vec![None; self.width * self.height];
+        let mut heap = BinaryHeap::new();
+        spent[self.offset(from)] = 0;
+        heap.push(Reverse((estimate(from), 0, from.x, from.y)));
+
+        while let Some(Reverse((_, so_far, x, y))) = heap.pop() {
+            let c = Coord::new(x, y);
+            if c == to {
+                return Some(self.trace(&came_by, from, to));
+            }
+            if so_far > spent[self.offset(c)] {
+                // Already reached more cheaply.
+                continue;
+            }
+            for &dir in connectivity.directions() {
+                let next = c.step(dir);
+                let step = match self.get(next).and_then(|cell| cost(next, cell)) {
+                    Some(step) => step,
+                    None => continue,
+                };
+                let total = so_far + step;
+                let idx = self.offset(next);
+                if total < spent[idx] {
+                    spent[idx] = total;
+                    came_by[idx] = Some(dir);
+                    heap.push(Reverse((total + estimate(next), total, next.x, next.y)));
+                }
+            }
+        }
+        None
+    }
+
+    fn offset(&self, c: Coord) -> usize {
+        c.y as usize * self.width + c.x as usize
+    }
+
+    /// Follow the moves that first reached each cell back from `to`.
+    fn trace(&self, came_by: &[Option<Direction>], from: Coord, to: Coord) -> Vec<Direction> {
+        let mut path = vec![];
+        let mut c = to;
+        while c != from {
+            let dir = came_by[self.offset(c)].unwrap();
+            path.push(dir);
+            c = c.step(dir.opposite());
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Connectivity, Heuristic};
+    use grid::{Board, Cell, Coord, Direction};
+    use test::Bencher;
+    use test_support::next_random;
+
+    /// A pseudo-random board with roughly one cell in `walls` a wall.
+    fn random_board(seed: &mut u64, width: usize, height: usize, walls: u64) -> Board {
+        let mut board = Board::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                if (next_random(seed) >> 33).is_multiple_of(walls) {
+                    board[Coord::new(x as i32, y as i32)] = Cell::Wall;
+                }
+            }
+        }
+        board
+    }
+
+    /// A weight from 1 to 9 for each position, or `None` for walls.
+    fn weight(board: &Board, c: Coord) -> Option<u64> {
+        match board[c] {
+            Cell::Wall => None,
+            _ => Some(1 + (c.x as u64 * 7 + c.y as u64 * 13) % 9),
+        }
+    }
+
+    /// Walk `path` from `from`, checking every cell entered, and return the
+    /// cost and where it ends up.
+    fn walk<F>(board: &Board, from: Coord, path: &[Direction], cost: F) -> (u64, Coord)
+        where F: Fn(Coord) -> Option<u64>
+    {
+        path.iter().fold((0, from), |(total, c), &dir| {
+            let next = c.step(dir);
+            assert!(board.contains(next), "{:?} is off the board", next);
+            (total + cost(next).expect("walked into a wall"), next)
+        })
+    }
+
+    /// The cheapest cost of reaching each cell from `from`, by Bellman–Ford.
+    fn brute_force<F>(board: &Board, from: Coord, connectivity: Connectivity, cost: F) -> Vec<Vec<Option<u64>>>
+        where F: Fn(Coord) -> Option<u64>
+    {
+        let mut best = vec![vec![None; board.width()]; board.height()];
+        best[from.y as usize][from.x as usize] = Some(0);
+        loop {
+            let mut changed = false;
+            for (c, _) in board {
+                let here = match best[c.y as usize][c.x as usize] {
+                    Some(here) => here,
+                    None => continue,
+                };
+                for &dir in connectivity.directions() {
+                    let next = c.step(dir);
+                    if !board.contains(next) {
+                        continue;
+                    }
+                    if let Some(step) = cost(next) {
+                        let slot = &mut best[next.y as usize][next.x as usize];
+                        if slot.is_none_or(|old| here + step < old) {
+                            *slot = Some(here + step);
+                            changed = true;
+                        }
+                    }
+                }
+            }
+            if !changed {
+                return best;
+            }
+        }
+    }
+
+    #[test]
+    fn test_maze() {
+        let board: Board = [
+            "m#---",
+            "-#-#-",
+            "-#-#-",
+            "---#p",
+        ].join("\n").parse().unwrap();
+        let (from, to) = (Coord::new(0, 0), Coord::new(4, 3));
+        let open = |c| c != Cell::Wall;
+
+        let path = board.bfs(from, to, Connectivity::Four, open).unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!(walk(&board, from, &path, |c| weight(&board, c).map(|_| 1)), (13, to));
+
+        let diagonal = board.bfs(from, to, Connectivity::Eight, open).unwrap();
+        assert_eq!(diagonal.len(), 9);
+        assert!(diagonal.contains(&Direction::UpRight));
+
+        assert_eq!(board.bfs(from, from, Connectivity::Four, open), Some(vec![]));
+        assert_eq!(board.bfs(from, Coord::new(5, 0), Connectivity::Four, open), None);
+        let mut sealed = board.clone();
+        sealed[Coord::new(4, 2)] = Cell::Wall;
+        assert_eq!(sealed.bfs(from, to, Connectivity::Four, open), None);
+        assert_eq!(sealed.dijkstra(from, to, Connectivity::Four, |c, _| weight(&sealed, c)), None);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut seed = 3;
+        for round in 0..60 {
+            let board = random_board(&mut seed, 3 + round % 11, 2 + round % 7, 4);
+            let from = Coord::new(0, 0);
+            for &(connectivity, heuristic) in &[(Connectivity::Four, Heuristic::Manhattan),
+                                                (Connectivity::Eight, Heuristic::Chebyshev)] {
+                let unit = |c: Coord| weight(&board, c).map(|_| 1);
+                let weighted = |c: Coord| weight(&board, c);
+                let steps = brute_force(&board, from, connectivity, unit);
+                let costs = brute_force(&board, from, connectivity, weighted);
+
+                for (to, _) in &board {
+                    let (want_steps, want_cost) = (steps[to.y as usize][to.x as usize],
+                                                   costs[to.y as usize][to.x as usize]);
+                    let found = board.bfs(from, to, connectivity, |c| c != Cell::Wall);
+                    assert_eq!(found.map(|p| walk(&board, from, &p, unit)), want_steps.map(|s| (s, to)));
+
+                    let found = board.astar(from, to, connectivity, heuristic, |c, _| unit(c));
+                    assert_eq!(found.map(|p| walk(&board, from, &p, unit)), want_steps.map(|s| (s, to)));
+
+                    let dijkstra = board.dijkstra(from, to, connectivity, |c, _| weighted(c));
+                    let astar = board.astar(from, to, connectivity, heuristic, |c, _| weighted(c));
+                    for found in [dijkstra, astar] {
+                        assert_eq!(found.map(|p| walk(&board, from, &p, weighted)), want_cost.map(|s| (s, to)),
+                                   "{:?} to {:?}", connectivity, to);
+                    }
+                }
+            }
+        }
+    }
+
+    #[bench]
+    fn bench_astar_500x500(b: &mut Bencher) {
+        let mut seed = 7;
+        let board = random_board(&mut seed, 500, 500, 5);
+        let (from, to) = (Coord::new(0, 0), Coord::new(499, 499));
+        let cost = |_, c| if c == Cell::Wall { None } else { Some(1) };
+        b.iter(|| board.astar(from, to, Connectivity::Four, Heuristic::Manhattan, cost));
+    }
+}
@@ -0,0 +1,54 @@
+//! Code shared between the individual challenge solutions.
+
+#![cfg_attr(test, feature(test))]
+#[cfg(test)]
+extern crate test;
+
+use std::{io, process};
+
+#[macro_use]
+mod macros;
+
+pub mod bigint;
+pub mod bot;
+pub mod combinatorics;
+pub mod decimal;
+pub mod error;
+pub mod factorial;
+pub mod fib;
+pub mod golden;
+pub mod grid;
+pub mod matrix;
+pub mod modint;
+pub mod number_theory;
+pub mod polynomial;
+pub mod rational;
+pub mod recurrence;
+pub mod ring;
+pub mod scanner;
+#[cfg(test)]
+mod test_support;
+
+pub use bigint::BigInt;
+pub use decimal::Decimal;
+pub use error::InputError;
+pub use grid::{Board, Coord, Direction};
+pub use matrix::{Matrix, SMatrix};
+pub use modint::{DynModInt, ModInt};
+pub use polynomial::Polynomial;
+pub use rational::Rational;
+pub use recurrence::LinearRecurrence;
+pub use ring::{Ring, Semiring};
+pub use scanner::Scanner;
+
+/// Run a solution against stdin. If it fails, print where the input went
+/// wrong and exit with a non-zero status.
+pub fn run<F>(solve: F)
+    where F: FnOnce(&mut Scanner<io::StdinLock<'static>>) -> Result<(), InputError>
+{
+    let mut scan = Scanner::stdin();
+    if let Err(err) = solve(&mut scan) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,45 @@
+/// Implement an operator for every mix of owned and borrowed operands, and
+/// its assignment form, in terms of the `&T op &T` impl. Generic types list
+/// their parameters first: `forward_binop!(impl[T: Ring] Matrix<T>: ...)`.
+macro_rules! forward_binop {
+    ($t:ident: $imp:ident, $method:ident, $assign_imp:ident, $assign_method:ident) => {
+        forward_binop!(impl[] $t: $imp, $method, $assign_imp, $assign_method);
+    };
+    (impl[$($gen:tt)*] $t:ty: $imp:ident, $method:ident, $assign_imp:ident, $assign_method:ident) => {
+        impl<$($gen)*> $imp<$t> for $t {
+            type Output = $t;
+
+            fn $method(self, rhs: $t) -> $t {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl<$($gen)*> $imp<&$t> for $t {
+            type Output = $t;
+
+            fn $method(self, rhs: &$t) -> $t {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl<$($gen)*> $imp<$t> for &$t {
+            type Output = $t;
+
+            fn $method(self, rhs: $t) -> $t {
+                self.$method(&rhs)
+            }
+        }
+
+        impl<$($gen)*> $assign_imp<$t> for $t {
+            fn $assign_method(&mut self, rhs: $t) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+
+        impl<$($gen)*> $assign_imp<&$t> for $t {
+            fn $assign_method(&mut self, rhs: &$t) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+    };
+}
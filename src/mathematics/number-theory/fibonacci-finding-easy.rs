@@ -1,897 +1,106 @@
 #![feature(test)]
 extern crate test;
+extern crate hackerrank;
 
-use std::io;
+use hackerrank::{ModInt, SMatrix};
+use hackerrank::modint::MOD_1E9_7;
 
-static MODULO: i64 = 1_000_000_007;
+type Matrix<const M: u64> = SMatrix<ModInt<M>, 2, 2>;
 
-fn read_line() -> String {
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    input
+fn fibonacci<const M: u64>(f0: ModInt<M>, f1: ModInt<M>, n: u64) -> ModInt<M> {
+    let (zero, one) = (ModInt::new(0), ModInt::new(1));
+    let fib_mat: Matrix<M> = SMatrix::new([[one, one], [one, zero]]);
+    let n_mat = fib_mat.pow(n);
+    let in_mat = SMatrix::new([[f1, f0]]);
+    let res_mat = in_mat * n_mat;
+    res_mat[(0, 1)]
 }
 
-fn read_num() -> i64 {
-    let line = read_line();
-    line.trim().parse().unwrap()
-}
-
-fn read_array() -> Vec<i64> {
-    let line = read_line();
-    line.trim().split(' ').map(|s| { s.parse().unwrap() }).collect()
-}
-
-fn read_triplet() -> (i64, i64, i64) {
-    let nums = read_array();
-    assert!(nums.len() == 3);
-    (nums[0], nums[1], nums[2])
+#[cfg_attr(test, allow(dead_code))]
+fn main() {
+    hackerrank::run(|scan| {
+        let count: i64 = scan.read()?;
+        for _ in 0..count {
+            let (f0, f1, n): (ModInt<MOD_1E9_7>, _, _) = scan.tuple()?;
+            println!("{}", fibonacci(f0, f1, n));
+        }
+        Ok(())
+    });
 }
 
-type Matrix = Vec<Vec<i64>>;
-
-fn mat_mult(a: &Matrix, b: &Matrix) -> Matrix {
-    let aw = a.len();
-    let ah = a[0].len();
-    let bw = b.len();
-    let bh = b[0].len();
+#[cfg(test)]
+mod tests {
+    use super::{fibonacci, Matrix};
+    use hackerrank::{ModInt, SMatrix};
+    use hackerrank::modint::MOD_1E9_7;
+    use test::Bencher;
 
-    assert!(ah == bw);
-    let mut output: Matrix = Vec::with_capacity(aw);
+    type Mod = ModInt<MOD_1E9_7>;
 
-    for x in 0..aw {
-        output.push(Vec::with_capacity(bh));
-        for y in 0..bh {
-            output[x].push(0);
-            for n in 0..ah {
-                output[x][y] += (a[x][n] * b[n][y]) % MODULO;
-            }
-            output[x][y] = output[x][y] % MODULO;
-        }
+    fn matrix(rows: [[u64; 2]; 2]) -> Matrix<MOD_1E9_7> {
+        SMatrix::new(rows.map(|row| row.map(ModInt::new)))
     }
 
-    output
-}
-
-fn mat_pow(a: &Matrix, n: i64) -> Matrix {
-    assert!(n > 0);
-    match n {
-        1 => a.clone(),
-        n if n % 2 == 0 => {
-            let b = mat_pow(a, n / 2);
-            mat_mult(&b, &b)
-        },
-        n if n % 2 == 1 => {
-            let b = mat_pow(a, n - 1);
-            mat_mult(&a, &b)
-        },
-        _ => unreachable!()
+    fn fib(f0: u64, f1: u64, n: u64) -> u64 {
+        fibonacci(Mod::new(f0), Mod::new(f1), n).value()
     }
-}
 
-fn fibonacci(f0: i64, f1: i64, n: i64) -> i64 {
-    let fib_mat = vec![vec![1, 1], vec![1, 0]];
-    let n_mat = mat_pow(&fib_mat, n);
-    let in_mat = vec![vec![f1, f0]];
-    let res_mat = mat_mult(&in_mat, &n_mat);
-    res_mat[0][1]
-}
-
-#[cfg_attr(test, allow(dead_code))]
-fn main() {
-    let count = read_num();
-    for _ in 0..count {
-        let (f0, f1, n) = read_triplet();
-        println!("{}", fibonacci(f0, f1, n));
+    #[test]
+    fn basic_examples() {
+        assert!(fib(2, 3, 1) == 3);
+        assert!(fib(9, 1, 7) == 85);
+        assert!(fib(9, 8, 3) == 25);
+        assert!(fib(2, 4, 9) == 178);
+        assert!(fib(1, 7, 2) == 8);
+        assert!(fib(1, 8, 1) == 8);
+        assert!(fib(4, 3, 1) == 3);
+        assert!(fib(3, 7, 5) == 44);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::{fibonacci, mat_mult, mat_pow, Matrix};
-    use test::Bencher;
+    #[test]
+    fn zeroth_term() {
+        assert_eq!(fib(2, 3, 0), 2);
+        assert_eq!(fib(0, 1, 0), 0);
+        assert!(matrix([[1, 1], [1, 0]]).pow(0) == matrix([[1, 0], [0, 1]]));
+    }
 
     #[test]
-    fn basic_examples() {
-        assert!(fibonacci(2, 3, 1) == 3);
-        assert!(fibonacci(9, 1, 7) == 85);
-        assert!(fibonacci(9, 8, 3) == 25);
-        assert!(fibonacci(2, 4, 9) == 178);
-        assert!(fibonacci(1, 7, 2) == 8);
-        assert!(fibonacci(1, 8, 1) == 8);
-        assert!(fibonacci(4, 3, 1) == 3);
-        assert!(fibonacci(3, 7, 5) == 44);
+    fn other_moduli() {
+        // F(2, 4, 9) = 178.
+        assert_eq!(fibonacci(ModInt::<10>::new(2), ModInt::new(4), 9).value(), 8);
+        assert_eq!(fibonacci(ModInt::<97>::new(2), ModInt::new(4), 9).value(), 178 % 97);
+        // F(60) = 1548008755920, and 60 is the Pisano period of 10.
+        assert_eq!(fibonacci(ModInt::<10>::new(0), ModInt::new(1), 60).value(), 0);
+        assert_eq!(fibonacci(ModInt::<10>::new(0), ModInt::new(1), 61).value(), 1);
     }
 
     #[test]
     fn test_mat_mult_1() {
-        let a: Matrix = vec![vec![1, 2], vec![3, 4]];
-        let b: Matrix = vec![vec![5, 6], vec![7, 8]];
-        let c: Matrix = mat_mult(&a, &b);
-        assert!(c == vec![vec![19, 22], vec![43, 50]]);
+        let a = matrix([[1, 2], [3, 4]]);
+        let b = matrix([[5, 6], [7, 8]]);
+        let c = a * b;
+        assert!(c == matrix([[19, 22], [43, 50]]));
     }
 
     #[test]
     fn test_mat_pow_1() {
-        let a: Matrix = vec![vec![1, 1], vec![1, 0]];
-        let b: Matrix = mat_pow(&a, 7);
-        assert!(b == vec![vec![21, 13], vec![13, 8]]);
+        let a = matrix([[1, 1], [1, 0]]);
+        let b = a.pow(7);
+        assert!(b == matrix([[21, 13], [13, 8]]));
     }
 
     #[bench]
     fn fast_enough_fibonacci(b: &mut Bencher) {
         b.iter(|| {
-            fibonacci(509618737, 460201239, 229176339)
+            fib(509618737, 460201239, 229176339)
         });
     }
 
     #[bench]
     fn fast_enough_mat_pow(b: &mut Bencher) {
-        let mat = vec![vec![1, 1], vec![1, 0]];
+        let mat = matrix([[1, 1], [1, 0]]);
         b.iter(|| {
-            mat_pow(&mat, 100)
+            mat.pow(100)
         });
     }
-
-    #[cfg_attr(not(feature = "expensive_tests"), ignore)]
-    #[test]
-    fn test_case_1() {
-        assert!(fibonacci(509618737, 460201239, 229176339) == 945141656);
-        assert!(fibonacci(749637804, 341825783, 626796445) == 129470519);
-        assert!(fibonacci(479138671, 857894620, 317640331) == 332746821);
-        assert!(fibonacci(637580144, 998104183, 111680626) == 240774240);
-        assert!(fibonacci(266164899, 535498701, 853818301) == 324701400);
-        assert!(fibonacci(839704015, 887153154, 280387684) == 713052478);
-        assert!(fibonacci(369755812, 90108128, 557805967) == 346993278);
-        assert!(fibonacci(749663976, 914901371, 435722412) == 2728628);
-        assert!(fibonacci(243219742, 210651272, 751393119) == 591382634);
-        assert!(fibonacci(309141944, 57841107, 800472445) == 546504724);
-        assert!(fibonacci(914751566, 537335946, 488016696) == 808799372);
-        assert!(fibonacci(154696840, 497265089, 213360966) == 491184943);
-        assert!(fibonacci(674527906, 126287863, 969679429) == 728393523);
-        assert!(fibonacci(529274496, 903934008, 136354579) == 244211854);
-        assert!(fibonacci(701815324, 136862318, 150988392) == 363303266);
-        assert!(fibonacci(929074747, 608997795, 558968308) == 993635639);
-        assert!(fibonacci(554911270, 383731366, 183153758) == 726952352);
-        assert!(fibonacci(18887329, 383640322, 463399349) == 821200366);
-        assert!(fibonacci(200623043, 759954761, 545299868) == 937038433);
-        assert!(fibonacci(793453926, 506418062, 131174511) == 224957331);
-        assert!(fibonacci(875403325, 169996727, 800053513) == 663636696);
-        assert!(fibonacci(517167886, 722828577, 460768237) == 232780875);
-        assert!(fibonacci(623663929, 111468059, 38233613) == 318866210);
-        assert!(fibonacci(640457336, 165176110, 760865292) == 81704818);
-        assert!(fibonacci(310708255, 840481315, 186174119) == 207949600);
-        assert!(fibonacci(421451388, 47848652, 778039054) == 796592013);
-        assert!(fibonacci(284734020, 314144994, 601653476) == 250859870);
-        assert!(fibonacci(192424812, 754230674, 674109741) == 624713333);
-        assert!(fibonacci(617318688, 895864237, 153695625) == 407336527);
-        assert!(fibonacci(24617348, 954073797, 737310327) == 885450415);
-        assert!(fibonacci(668061097, 881073979, 619869800) == 370239492);
-        assert!(fibonacci(838504918, 653871171, 733937282) == 365766142);
-        assert!(fibonacci(336301066, 184647439, 414033740) == 401601924);
-        assert!(fibonacci(690220155, 305410818, 497529737) == 877167310);
-        assert!(fibonacci(520734695, 914453934, 218555257) == 338901263);
-        assert!(fibonacci(422288466, 708179074, 543159006) == 340296355);
-        assert!(fibonacci(277225230, 779171655, 712106109) == 704672373);
-        assert!(fibonacci(767260814, 508719907, 192273068) == 262416135);
-        assert!(fibonacci(529521034, 682978513, 415766053) == 182285674);
-        assert!(fibonacci(125943772, 899849197, 826964340) == 411856885);
-        assert!(fibonacci(307072612, 599046582, 157394262) == 582727883);
-        assert!(fibonacci(300923285, 972396238, 284102130) == 678516861);
-        assert!(fibonacci(140995492, 472203336, 186610145) == 229672570);
-        assert!(fibonacci(452236837, 85150323, 863201213) == 648703875);
-        assert!(fibonacci(364005314, 594513864, 8734177) == 56034682);
-        assert!(fibonacci(104123740, 671690116, 839776739) == 167662472);
-        assert!(fibonacci(455554485, 195030223, 187685164) == 861699884);
-        assert!(fibonacci(610536618, 747392117, 174902142) == 70325888);
-        assert!(fibonacci(25204218, 900800283, 372354073) == 468644196);
-        assert!(fibonacci(427596733, 308983885, 970892658) == 196402805);
-        assert!(fibonacci(318171229, 210357294, 284798242) == 752573858);
-        assert!(fibonacci(587069399, 383147544, 706364235) == 850509879);
-        assert!(fibonacci(340135475, 219811411, 942057696) == 725294678);
-        assert!(fibonacci(934453126, 281292974, 235975739) == 857625259);
-        assert!(fibonacci(356548862, 824988393, 694021332) == 147760733);
-        assert!(fibonacci(848904896, 403255500, 914206042) == 657680138);
-        assert!(fibonacci(183538892, 425397294, 11288397) == 211319706);
-        assert!(fibonacci(575989314, 670389287, 704818974) == 514672805);
-        assert!(fibonacci(639279177, 696535994, 851654464) == 652167184);
-        assert!(fibonacci(982492119, 275719067, 71595956) == 254941543);
-        assert!(fibonacci(911748057, 713398758, 163219451) == 827445371);
-        assert!(fibonacci(215717487, 134065608, 874793028) == 860634947);
-        assert!(fibonacci(578402972, 776935915, 211366321) == 756242997);
-        assert!(fibonacci(302369941, 763988264, 451878706) == 237936951);
-        assert!(fibonacci(897719043, 521101358, 913737248) == 220822151);
-        assert!(fibonacci(951709425, 253987756, 922403723) == 554198975);
-        assert!(fibonacci(480880810, 176299322, 497544783) == 353187174);
-        assert!(fibonacci(458148030, 244057842, 883586590) == 294619368);
-        assert!(fibonacci(959604261, 742181915, 539968006) == 180510098);
-        assert!(fibonacci(579979267, 947790222, 686611549) == 789226590);
-        assert!(fibonacci(854709771, 357643356, 944092344) == 956138176);
-        assert!(fibonacci(870461741, 22705070, 567894215) == 240321853);
-        assert!(fibonacci(72756288, 140831375, 690922786) == 120458864);
-        assert!(fibonacci(819228303, 270501925, 626319585) == 21460598);
-        assert!(fibonacci(702839722, 881168950, 661409030) == 41671085);
-        assert!(fibonacci(559409691, 678270270, 149287930) == 975669531);
-        assert!(fibonacci(791081726, 687569752, 442548238) == 230164698);
-        assert!(fibonacci(929250740, 501611310, 99419746) == 761752869);
-        assert!(fibonacci(574051172, 453600027, 469340917) == 600621981);
-        assert!(fibonacci(279632861, 256113225, 391883693) == 691187100);
-        assert!(fibonacci(334825023, 998423704, 829145692) == 232729529);
-        assert!(fibonacci(524754771, 447660170, 406344909) == 330916874);
-        assert!(fibonacci(507786362, 27257303, 498396289) == 793420813);
-        assert!(fibonacci(345843033, 878953137, 113156382) == 980365965);
-        assert!(fibonacci(231480938, 661652506, 905797396) == 160348502);
-        assert!(fibonacci(871225197, 755308307, 362888891) == 181864693);
-        assert!(fibonacci(222177561, 400194571, 63911645) == 948842671);
-        assert!(fibonacci(390680077, 788897540, 260220471) == 294527360);
-        assert!(fibonacci(244063312, 925459030, 856032046) == 38864339);
-        assert!(fibonacci(844672598, 296410569, 569105042) == 40031616);
-        assert!(fibonacci(98553298, 793123426, 884718149) == 220639608);
-        assert!(fibonacci(299039094, 484403281, 272078220) == 72431693);
-        assert!(fibonacci(797173893, 178084951, 433508781) == 789821271);
-        assert!(fibonacci(255064122, 51623330, 651012968) == 196196043);
-        assert!(fibonacci(650891640, 445238693, 808616614) == 805013776);
-        assert!(fibonacci(329391857, 697769803, 839958803) == 615268360);
-        assert!(fibonacci(193622349, 702825974, 698291720) == 959656029);
-        assert!(fibonacci(106452027, 57455300, 855918654) == 160526035);
-        assert!(fibonacci(327972048, 944144946, 209526164) == 503236107);
-        assert!(fibonacci(568925221, 280691460, 522201140) == 783095622);
-        assert!(fibonacci(550312862, 663113763, 730846733) == 618641079);
-        assert!(fibonacci(929291246, 247289735, 85829712) == 49020103);
-        assert!(fibonacci(228438232, 932441844, 177249226) == 28079657);
-        assert!(fibonacci(633719177, 74051305, 577223357) == 461530739);
-        assert!(fibonacci(929380109, 967113439, 348571242) == 789713085);
-        assert!(fibonacci(412898677, 739788436, 343658848) == 698471107);
-        assert!(fibonacci(451603857, 914671454, 227689227) == 731675832);
-        assert!(fibonacci(16073243, 651050250, 593584594) == 156270713);
-        assert!(fibonacci(870813322, 992101271, 735668127) == 334036058);
-        assert!(fibonacci(28799496, 971067045, 540258335) == 253993630);
-        assert!(fibonacci(62552056, 228248672, 897393491) == 511476158);
-        assert!(fibonacci(911307641, 704751259, 388509566) == 29655088);
-        assert!(fibonacci(920166234, 721600393, 197948958) == 377246733);
-        assert!(fibonacci(722786903, 100953626, 765327958) == 219865688);
-        assert!(fibonacci(662709578, 559903172, 628774670) == 498354454);
-        assert!(fibonacci(121068364, 177071918, 90341861) == 806121857);
-        assert!(fibonacci(507347413, 915073371, 204356510) == 376679635);
-        assert!(fibonacci(865867316, 117321365, 366020006) == 315263741);
-        assert!(fibonacci(294511913, 534239620, 12063513) == 628352388);
-        assert!(fibonacci(137262140, 58477925, 255188464) == 685652293);
-        assert!(fibonacci(350161584, 199638736, 961374798) == 164366444);
-        assert!(fibonacci(636990891, 571167121, 845802633) == 478502376);
-        assert!(fibonacci(679829865, 18072469, 262362180) == 711376001);
-        assert!(fibonacci(960061675, 492732442, 18188043) == 305361782);
-        assert!(fibonacci(145709890, 728816954, 813717829) == 355306737);
-        assert!(fibonacci(462361269, 353363664, 91147079) == 405813093);
-        assert!(fibonacci(964809924, 749744959, 815029353) == 529327265);
-        assert!(fibonacci(645326266, 521452082, 55993675) == 686196193);
-        assert!(fibonacci(335901825, 196684740, 110927308) == 87181101);
-        assert!(fibonacci(531373486, 616795729, 170511639) == 764979424);
-        assert!(fibonacci(376446054, 782904095, 663122469) == 562933682);
-        assert!(fibonacci(942760494, 372625319, 901314889) == 381416155);
-        assert!(fibonacci(803953159, 25718688, 988736050) == 517888039);
-        assert!(fibonacci(782972036, 441238498, 158999450) == 581096922);
-        assert!(fibonacci(827979681, 547285738, 422340929) == 666080929);
-        assert!(fibonacci(186168468, 720157426, 388504410) == 187662322);
-        assert!(fibonacci(552302176, 832150643, 180875956) == 335315410);
-        assert!(fibonacci(920916433, 172452216, 308732966) == 390541342);
-        assert!(fibonacci(440159110, 704835318, 678186654) == 482431716);
-        assert!(fibonacci(905381124, 301089066, 374482381) == 149759421);
-        assert!(fibonacci(734875325, 148456380, 401276740) == 851097988);
-        assert!(fibonacci(91850542, 583615622, 709828347) == 890444313);
-        assert!(fibonacci(355065574, 202949396, 356191636) == 471344219);
-        assert!(fibonacci(912402939, 658408109, 327644977) == 331925516);
-        assert!(fibonacci(102411028, 181837888, 308506462) == 231179356);
-        assert!(fibonacci(656029904, 817346585, 974166344) == 596728195);
-        assert!(fibonacci(633652746, 149733358, 476527313) == 468972574);
-        assert!(fibonacci(722422897, 979071309, 784645085) == 174040734);
-        assert!(fibonacci(989635682, 455529620, 610451880) == 375716266);
-        assert!(fibonacci(354389503, 502601384, 667790000) == 850697089);
-        assert!(fibonacci(223128236, 898572035, 724629622) == 405517831);
-        assert!(fibonacci(614253669, 48096712, 292782118) == 574543971);
-        assert!(fibonacci(757722709, 736129140, 963670115) == 689932173);
-        assert!(fibonacci(712512581, 831102893, 517208030) == 979932917);
-        assert!(fibonacci(32312775, 639899237, 173742535) == 390181775);
-        assert!(fibonacci(853230979, 818505406, 990614327) == 173458045);
-        assert!(fibonacci(226505, 784129205, 887488733) == 894783043);
-        assert!(fibonacci(704020309, 271728378, 151355708) == 904718318);
-        assert!(fibonacci(897955067, 12452428, 169385071) == 114404126);
-        assert!(fibonacci(616631654, 102214859, 128086003) == 65918931);
-        assert!(fibonacci(99376468, 676071, 700348012) == 884094726);
-        assert!(fibonacci(688401635, 689274704, 759836074) == 243014248);
-        assert!(fibonacci(603015355, 488157359, 133741177) == 511728040);
-        assert!(fibonacci(15724344, 898307194, 81217445) == 653669924);
-        assert!(fibonacci(10496229, 921140164, 318630464) == 653267716);
-        assert!(fibonacci(959319283, 690110123, 339172073) == 697088262);
-        assert!(fibonacci(610902551, 136404703, 637024214) == 52392714);
-        assert!(fibonacci(619837552, 354162999, 718472178) == 199777057);
-        assert!(fibonacci(780301266, 519107926, 626843658) == 521521056);
-        assert!(fibonacci(573273914, 716298601, 35644284) == 187492043);
-        assert!(fibonacci(123397048, 141091056, 633914281) == 826415105);
-        assert!(fibonacci(835584113, 613136114, 830426823) == 533567305);
-        assert!(fibonacci(816860018, 343911139, 950624533) == 242879742);
-        assert!(fibonacci(413906460, 250215625, 330348047) == 828336157);
-        assert!(fibonacci(856873151, 984502160, 885822010) == 435703771);
-        assert!(fibonacci(806271288, 693524081, 350588213) == 684868846);
-        assert!(fibonacci(832725244, 938635784, 322342304) == 955135843);
-        assert!(fibonacci(830212998, 5729104, 965810155) == 862875866);
-        assert!(fibonacci(491061788, 479538915, 646513071) == 886716107);
-        assert!(fibonacci(981875833, 908100368, 170166778) == 430699894);
-        assert!(fibonacci(132992417, 29741441, 771858757) == 595439921);
-        assert!(fibonacci(98096893, 892327296, 757216139) == 891690322);
-        assert!(fibonacci(447303164, 174912115, 308004051) == 670880608);
-        assert!(fibonacci(488203641, 142459265, 346198985) == 87910394);
-        assert!(fibonacci(388547540, 196996091, 886189078) == 9831619);
-        assert!(fibonacci(306676167, 762964401, 369660839) == 934440975);
-        assert!(fibonacci(832650167, 974029978, 825583845) == 94230354);
-        assert!(fibonacci(276255445, 740548669, 777662817) == 450071426);
-        assert!(fibonacci(713301979, 293184049, 135361952) == 900556964);
-        assert!(fibonacci(668104126, 344522326, 133597199) == 194619105);
-        assert!(fibonacci(183913752, 834984184, 435810770) == 495893356);
-        assert!(fibonacci(780457755, 280914044, 220474184) == 611443956);
-        assert!(fibonacci(558489290, 758776258, 92174864) == 310760794);
-        assert!(fibonacci(128605871, 358968125, 518611966) == 313359192);
-        assert!(fibonacci(42584163, 344521603, 796176519) == 519875533);
-        assert!(fibonacci(976143319, 441665459, 808733013) == 741242189);
-        assert!(fibonacci(79621078, 184385622, 716574514) == 699410857);
-        assert!(fibonacci(276852233, 149225666, 934070390) == 121405239);
-        assert!(fibonacci(344582933, 856736972, 289192771) == 709233215);
-        assert!(fibonacci(994195940, 384794914, 599143247) == 964802097);
-        assert!(fibonacci(621854188, 779199037, 830389789) == 162644777);
-        assert!(fibonacci(174406853, 304289889, 307475081) == 247045870);
-        assert!(fibonacci(910388215, 608089784, 916082046) == 73822269);
-        assert!(fibonacci(665714894, 748186877, 4188143) == 618150386);
-        assert!(fibonacci(277485975, 704044297, 615061853) == 720684958);
-        assert!(fibonacci(306971879, 233671282, 396027067) == 641787500);
-        assert!(fibonacci(981486297, 737158660, 851518590) == 382596092);
-        assert!(fibonacci(326628939, 588483048, 160136704) == 655345620);
-        assert!(fibonacci(417022684, 907061519, 685758519) == 34914099);
-        assert!(fibonacci(501740379, 435874822, 424177072) == 611716601);
-        assert!(fibonacci(931281412, 564293349, 373981345) == 435977210);
-        assert!(fibonacci(493031617, 506751683, 579769087) == 162913618);
-        assert!(fibonacci(688222177, 868177310, 40231715) == 524741778);
-        assert!(fibonacci(488701439, 65755104, 833575674) == 189344804);
-        assert!(fibonacci(892650966, 413906184, 436198745) == 952067224);
-        assert!(fibonacci(712386372, 999366257, 445181369) == 69135330);
-        assert!(fibonacci(319008537, 37611055, 623065690) == 451450930);
-        assert!(fibonacci(893165704, 12709644, 647636253) == 965527401);
-        assert!(fibonacci(747624657, 295225249, 190715989) == 487491077);
-        assert!(fibonacci(670253085, 757384168, 397228369) == 572997771);
-        assert!(fibonacci(621716561, 408647836, 172214963) == 385134482);
-        assert!(fibonacci(491904974, 734433481, 183893528) == 896376301);
-        assert!(fibonacci(630206797, 784454358, 197292614) == 400210633);
-        assert!(fibonacci(35292767, 618749701, 365493971) == 850182320);
-        assert!(fibonacci(355795352, 492784858, 671403556) == 986889559);
-        assert!(fibonacci(17942917, 433977973, 174576865) == 988042275);
-        assert!(fibonacci(723937959, 704636311, 907695261) == 928571698);
-        assert!(fibonacci(240577150, 182731842, 398263767) == 435479008);
-        assert!(fibonacci(801111382, 38115709, 551583706) == 320941498);
-        assert!(fibonacci(726345092, 745406959, 211526435) == 395854030);
-        assert!(fibonacci(389053098, 17969092, 110793143) == 380868404);
-        assert!(fibonacci(643003345, 866984877, 657107267) == 947994405);
-        assert!(fibonacci(661360711, 400745992, 679472703) == 614429770);
-        assert!(fibonacci(252305217, 82179576, 214911900) == 793612588);
-        assert!(fibonacci(247888756, 283715771, 418861354) == 560473202);
-        assert!(fibonacci(257571719, 537370353, 519924785) == 358252994);
-        assert!(fibonacci(976232696, 729681741, 861247276) == 279640510);
-        assert!(fibonacci(16139125, 946315126, 52747857) == 324995435);
-        assert!(fibonacci(489533108, 381139412, 225915995) == 611883181);
-        assert!(fibonacci(773951195, 690793592, 696317773) == 275093406);
-        assert!(fibonacci(632309821, 903861705, 39047399) == 142551395);
-        assert!(fibonacci(985766178, 646239668, 600780609) == 371737136);
-        assert!(fibonacci(254700829, 712792006, 625799980) == 322890467);
-        assert!(fibonacci(14296290, 356582205, 33231981) == 813550703);
-        assert!(fibonacci(495104162, 471632742, 622456736) == 387033716);
-        assert!(fibonacci(692783362, 992688394, 899016070) == 249845506);
-        assert!(fibonacci(979402413, 543539663, 500745355) == 538333431);
-        assert!(fibonacci(31658921, 750112395, 15725219) == 618296624);
-        assert!(fibonacci(350279158, 372913974, 71653966) == 587011599);
-        assert!(fibonacci(58045286, 153470238, 485845466) == 312154504);
-        assert!(fibonacci(431191273, 887409516, 709952400) == 388988010);
-        assert!(fibonacci(983154930, 619995396, 178317870) == 132670237);
-        assert!(fibonacci(175864501, 262122577, 637476103) == 497040678);
-        assert!(fibonacci(818080744, 2870891, 824578346) == 237634919);
-        assert!(fibonacci(894124805, 961936407, 373099536) == 677878858);
-        assert!(fibonacci(828015296, 521034963, 474682384) == 246351073);
-        assert!(fibonacci(430291121, 796749745, 388451018) == 304293465);
-        assert!(fibonacci(326899924, 794548782, 147253930) == 375284026);
-        assert!(fibonacci(195572418, 600650900, 153749310) == 189810579);
-        assert!(fibonacci(23322180, 635487021, 273325695) == 560096389);
-        assert!(fibonacci(529126643, 196655544, 559321769) == 426434986);
-        assert!(fibonacci(139954515, 583105016, 469172688) == 738023443);
-        assert!(fibonacci(323279580, 511949232, 851637346) == 1022429);
-        assert!(fibonacci(444138866, 516918861, 730565817) == 338491869);
-        assert!(fibonacci(261539968, 161321670, 540668772) == 236234038);
-        assert!(fibonacci(676167009, 137534116, 788175987) == 319428928);
-        assert!(fibonacci(642625682, 522263862, 851879010) == 410007204);
-        assert!(fibonacci(596971582, 627754165, 356720493) == 430039190);
-        assert!(fibonacci(97394449, 104291349, 92860735) == 928048153);
-        assert!(fibonacci(562698470, 787582513, 19344496) == 304609107);
-        assert!(fibonacci(24568561, 152542321, 626635556) == 454366537);
-        assert!(fibonacci(364150409, 288954101, 806215346) == 929370569);
-        assert!(fibonacci(265256578, 754170290, 378831392) == 899887221);
-        assert!(fibonacci(903926848, 504735716, 9085732) == 219291380);
-        assert!(fibonacci(623045038, 986152254, 279830885) == 251412569);
-        assert!(fibonacci(657885200, 65359957, 633227112) == 590729927);
-        assert!(fibonacci(606585157, 519405408, 463116784) == 664949385);
-        assert!(fibonacci(365573323, 380696498, 113223159) == 898442385);
-        assert!(fibonacci(421446684, 932155061, 568901379) == 311430708);
-        assert!(fibonacci(202601277, 42560067, 478813668) == 340745622);
-        assert!(fibonacci(376311954, 760581110, 724366719) == 34392413);
-        assert!(fibonacci(832292850, 419570306, 364376540) == 226951479);
-        assert!(fibonacci(103930262, 897389558, 872367568) == 466624525);
-        assert!(fibonacci(734453425, 410910432, 383363826) == 742249908);
-        assert!(fibonacci(409344596, 738698834, 17528147) == 764961570);
-        assert!(fibonacci(842793279, 973926544, 641601910) == 207312314);
-        assert!(fibonacci(76889609, 439509248, 38931393) == 209880964);
-        assert!(fibonacci(459633622, 956113312, 268177105) == 77365184);
-        assert!(fibonacci(556227711, 658995237, 771845823) == 287451344);
-        assert!(fibonacci(513412397, 942703725, 356799039) == 922113691);
-        assert!(fibonacci(237313968, 62655301, 711610224) == 953918399);
-        assert!(fibonacci(900017723, 527614894, 744154606) == 768512248);
-        assert!(fibonacci(284719013, 790752187, 566581948) == 322509613);
-        assert!(fibonacci(915454344, 553954939, 167970398) == 870808860);
-        assert!(fibonacci(760859544, 872131731, 108494977) == 938702369);
-        assert!(fibonacci(79752959, 956228726, 641997663) == 273325364);
-        assert!(fibonacci(95695013, 578653404, 958228517) == 999902175);
-        assert!(fibonacci(927299468, 125711669, 603050818) == 260307154);
-        assert!(fibonacci(439882275, 916678332, 804467797) == 205518260);
-        assert!(fibonacci(456189614, 276065139, 760575068) == 49989358);
-        assert!(fibonacci(592530716, 590517864, 954685833) == 576053495);
-        assert!(fibonacci(515568529, 497139458, 348255131) == 128841302);
-        assert!(fibonacci(671753601, 509326872, 211083941) == 435407831);
-        assert!(fibonacci(273373540, 558074267, 183174357) == 963907851);
-        assert!(fibonacci(75019962, 161435264, 885554309) == 933428604);
-        assert!(fibonacci(870960994, 698774078, 83981581) == 628212925);
-        assert!(fibonacci(159682129, 476474752, 702766511) == 462976492);
-        assert!(fibonacci(129848161, 417717384, 364050322) == 486665730);
-        assert!(fibonacci(398570522, 310014499, 936943632) == 445482733);
-        assert!(fibonacci(108559407, 460135449, 610936561) == 205995059);
-        assert!(fibonacci(939686808, 828529398, 514687048) == 418954455);
-        assert!(fibonacci(806006880, 322923628, 963437074) == 665499594);
-        assert!(fibonacci(213284565, 245291015, 374992274) == 972801956);
-        assert!(fibonacci(760239845, 407999358, 446901854) == 297288377);
-        assert!(fibonacci(430913722, 822321472, 20579137) == 67599181);
-        assert!(fibonacci(775054160, 852279507, 964276404) == 535030211);
-        assert!(fibonacci(717496508, 568921281, 217904255) == 423823523);
-        assert!(fibonacci(720486216, 296507486, 799590387) == 857490292);
-        assert!(fibonacci(57808558, 462682556, 172800480) == 730465984);
-        assert!(fibonacci(590635512, 116998008, 187124959) == 373397043);
-        assert!(fibonacci(411311499, 563194195, 49191424) == 459202423);
-        assert!(fibonacci(600147380, 333686732, 729544869) == 754618268);
-        assert!(fibonacci(668487309, 269013082, 838511636) == 703400122);
-        assert!(fibonacci(922117235, 657676429, 453483064) == 884831543);
-        assert!(fibonacci(708989307, 399442284, 68475395) == 478827722);
-        assert!(fibonacci(255864657, 698934438, 595395912) == 538971913);
-        assert!(fibonacci(343471185, 623516361, 457734992) == 699957662);
-        assert!(fibonacci(972667227, 391062898, 891214167) == 804419655);
-        assert!(fibonacci(393032306, 219200593, 532021913) == 734680569);
-        assert!(fibonacci(715096661, 748198322, 860241071) == 283393219);
-        assert!(fibonacci(790636594, 622649053, 128293007) == 389223856);
-        assert!(fibonacci(187867315, 348928347, 844805163) == 654957954);
-        assert!(fibonacci(397710431, 830766342, 488634741) == 227214372);
-        assert!(fibonacci(291034268, 106566852, 583266425) == 227352828);
-        assert!(fibonacci(125764769, 795379273, 911244852) == 518239124);
-        assert!(fibonacci(764421191, 11496909, 897065201) == 414394582);
-        assert!(fibonacci(731114992, 801943901, 763748118) == 596473399);
-        assert!(fibonacci(577404568, 247164327, 493481646) == 225604975);
-        assert!(fibonacci(729389967, 438644271, 172131297) == 518916923);
-        assert!(fibonacci(157977257, 207115074, 114486139) == 752166351);
-        assert!(fibonacci(197522956, 953390647, 520814759) == 362146037);
-        assert!(fibonacci(978270564, 131480641, 35027377) == 579516769);
-        assert!(fibonacci(325190057, 521121992, 50513938) == 495283659);
-        assert!(fibonacci(223670232, 858154226, 868168095) == 371593885);
-        assert!(fibonacci(106761171, 52436918, 516949510) == 246315164);
-        assert!(fibonacci(874468566, 846902459, 595683624) == 350041383);
-        assert!(fibonacci(979969315, 628611115, 207703684) == 262451075);
-        assert!(fibonacci(634956711, 983981668, 946254421) == 288790983);
-        assert!(fibonacci(96492954, 213232026, 375484726) == 988843542);
-        assert!(fibonacci(634811361, 458477348, 910284075) == 386527663);
-        assert!(fibonacci(672673866, 239733174, 623651269) == 202844998);
-        assert!(fibonacci(374148602, 93511312, 153176204) == 580837829);
-        assert!(fibonacci(62451666, 147494205, 663898633) == 415049345);
-        assert!(fibonacci(876217475, 439231135, 490374917) == 809211395);
-        assert!(fibonacci(846551264, 507444761, 943789674) == 130696409);
-        assert!(fibonacci(895580022, 470643398, 194727410) == 500362465);
-        assert!(fibonacci(976532136, 854921400, 591741811) == 269989473);
-        assert!(fibonacci(576447672, 178007942, 578503959) == 640247661);
-        assert!(fibonacci(906782455, 562566245, 969408978) == 897713377);
-        assert!(fibonacci(574560338, 881777610, 918248614) == 968892595);
-        assert!(fibonacci(659542650, 690378696, 62644644) == 19040337);
-        assert!(fibonacci(140229862, 550996365, 618421052) == 220219535);
-        assert!(fibonacci(244516827, 732612568, 958925606) == 624698724);
-        assert!(fibonacci(363773306, 812016901, 699408255) == 20696245);
-        assert!(fibonacci(931784991, 103751840, 189379980) == 198182261);
-        assert!(fibonacci(717328766, 788405446, 476536908) == 249046882);
-        assert!(fibonacci(2464747, 200912931, 742588628) == 619446836);
-        assert!(fibonacci(180757317, 658279224, 603555948) == 957667853);
-        assert!(fibonacci(318542265, 96226194, 61725233) == 189180539);
-        assert!(fibonacci(45147310, 467366147, 530945067) == 118205316);
-        assert!(fibonacci(183767226, 487891328, 265716594) == 232510654);
-        assert!(fibonacci(745650018, 216674826, 748852438) == 526839564);
-        assert!(fibonacci(427730273, 706321403, 956448396) == 332423482);
-        assert!(fibonacci(325368623, 651063196, 738030829) == 488422258);
-        assert!(fibonacci(235801803, 612758830, 42904499) == 722150961);
-        assert!(fibonacci(892333556, 644662681, 74256102) == 684773554);
-        assert!(fibonacci(389123979, 189453689, 774160798) == 900772181);
-        assert!(fibonacci(492872071, 572095591, 680864679) == 990040106);
-        assert!(fibonacci(175659987, 478785333, 32099472) == 636151318);
-        assert!(fibonacci(459088695, 821687596, 454770171) == 846891695);
-        assert!(fibonacci(556695819, 199369518, 265246421) == 532070639);
-        assert!(fibonacci(427980540, 180006081, 324125573) == 960142200);
-        assert!(fibonacci(433691661, 186134974, 887077014) == 158888838);
-        assert!(fibonacci(440527541, 289598494, 82084044) == 117146297);
-        assert!(fibonacci(520088512, 677096123, 549849735) == 86857927);
-        assert!(fibonacci(4557800, 944955513, 45520395) == 448194045);
-        assert!(fibonacci(560651449, 426208708, 451563513) == 468333376);
-        assert!(fibonacci(987469131, 656023330, 277912459) == 119571174);
-        assert!(fibonacci(756784268, 690895154, 915795736) == 343824956);
-        assert!(fibonacci(584851914, 569990032, 737889492) == 533556515);
-        assert!(fibonacci(716752967, 968641577, 884633806) == 785456834);
-        assert!(fibonacci(501678225, 768672804, 451693679) == 183568684);
-        assert!(fibonacci(472784408, 807821263, 432752749) == 105708966);
-        assert!(fibonacci(718778926, 458641895, 458527707) == 271413982);
-        assert!(fibonacci(187179088, 948596437, 899855195) == 421484814);
-        assert!(fibonacci(692076754, 972783559, 894999467) == 105661422);
-        assert!(fibonacci(131014944, 171102188, 533829820) == 195569087);
-        assert!(fibonacci(986579076, 898437246, 395478889) == 7578994);
-        assert!(fibonacci(3206658, 569226688, 543346187) == 647195861);
-        assert!(fibonacci(987333962, 671196271, 489110926) == 871432088);
-        assert!(fibonacci(408329837, 848839746, 616144942) == 25933461);
-        assert!(fibonacci(149187523, 723774574, 320956916) == 556149641);
-        assert!(fibonacci(197450237, 18410287, 908423319) == 437232525);
-        assert!(fibonacci(98156057, 531773391, 137134251) == 839369285);
-        assert!(fibonacci(612767645, 841872523, 967566812) == 482324996);
-        assert!(fibonacci(993035805, 800290044, 707426892) == 911009698);
-        assert!(fibonacci(378057264, 64707514, 718111595) == 523747155);
-        assert!(fibonacci(20796269, 453836970, 398887845) == 421601338);
-        assert!(fibonacci(204059672, 730173890, 70204332) == 418123420);
-        assert!(fibonacci(489154918, 498471568, 199446116) == 252409498);
-        assert!(fibonacci(908347491, 485450445, 136624993) == 974090164);
-        assert!(fibonacci(943641822, 310449089, 609802148) == 131665096);
-        assert!(fibonacci(842382765, 37991565, 224821864) == 779451889);
-        assert!(fibonacci(578898279, 494626518, 954373272) == 432491928);
-        assert!(fibonacci(986576147, 32858888, 639328796) == 589386320);
-        assert!(fibonacci(396695569, 373811432, 56564723) == 317565992);
-        assert!(fibonacci(427912076, 10170852, 768936644) == 111550882);
-        assert!(fibonacci(835919294, 609276699, 606488757) == 295420871);
-        assert!(fibonacci(770999331, 387533568, 395002777) == 776363080);
-        assert!(fibonacci(217257098, 945127850, 993600683) == 874998335);
-        assert!(fibonacci(250752584, 708295319, 519938719) == 219636505);
-        assert!(fibonacci(708977204, 189808566, 46322946) == 409968028);
-        assert!(fibonacci(509258, 669125823, 531423434) == 598915625);
-        assert!(fibonacci(357764664, 150653041, 762298479) == 152685286);
-        assert!(fibonacci(482605029, 799158984, 570080996) == 327336739);
-        assert!(fibonacci(763738323, 34220121, 420978083) == 143974108);
-        assert!(fibonacci(759559048, 807364103, 356362459) == 667257932);
-        assert!(fibonacci(13639609, 61242843, 488300716) == 978863366);
-        assert!(fibonacci(430509472, 72428198, 876173746) == 65838199);
-        assert!(fibonacci(530136235, 172642492, 922915521) == 924865469);
-        assert!(fibonacci(214799372, 625125668, 92863715) == 220068212);
-        assert!(fibonacci(231221180, 328145695, 786331198) == 382151697);
-        assert!(fibonacci(434434554, 277598944, 843050322) == 581625947);
-        assert!(fibonacci(593005851, 396186312, 704685609) == 578080743);
-        assert!(fibonacci(525141288, 70147412, 859517811) == 59751430);
-        assert!(fibonacci(6638166, 353314266, 810117715) == 973174626);
-        assert!(fibonacci(36407761, 7261009, 353313447) == 441912586);
-        assert!(fibonacci(974024693, 457698049, 137763748) == 652870067);
-        assert!(fibonacci(637238225, 237112976, 647052477) == 129421500);
-        assert!(fibonacci(31638003, 278467733, 117380368) == 901193375);
-        assert!(fibonacci(170149200, 470373023, 496483331) == 943258309);
-        assert!(fibonacci(608507913, 142965293, 525527373) == 475824037);
-        assert!(fibonacci(669434765, 251383849, 471013289) == 687599658);
-        assert!(fibonacci(783749797, 329303769, 756621177) == 982765654);
-        assert!(fibonacci(577927761, 166553198, 411177791) == 286389219);
-        assert!(fibonacci(651676850, 488498321, 991095430) == 927967784);
-        assert!(fibonacci(628782904, 423871307, 719113931) == 832048799);
-        assert!(fibonacci(66056032, 493728474, 165381484) == 406086124);
-        assert!(fibonacci(569602074, 240774679, 167427619) == 292560393);
-        assert!(fibonacci(955099966, 593982955, 91032720) == 830297714);
-        assert!(fibonacci(139278722, 402796551, 999131211) == 815394318);
-        assert!(fibonacci(725669954, 422856652, 925813288) == 604091540);
-        assert!(fibonacci(208202278, 916633375, 927182119) == 480793509);
-        assert!(fibonacci(333990439, 337203401, 101930418) == 509715307);
-        assert!(fibonacci(339104426, 252657963, 677957239) == 405037640);
-        assert!(fibonacci(596692270, 396096933, 291144852) == 581980552);
-        assert!(fibonacci(726585956, 985561374, 748614655) == 936499750);
-        assert!(fibonacci(655957046, 402855099, 854596426) == 740513322);
-        assert!(fibonacci(398266437, 104093168, 976644548) == 594426090);
-        assert!(fibonacci(331101847, 38905840, 902190613) == 402289797);
-        assert!(fibonacci(358099754, 714334798, 657400893) == 720624087);
-        assert!(fibonacci(379980570, 644471076, 926507218) == 703214692);
-        assert!(fibonacci(757489966, 852257807, 743696546) == 76767656);
-        assert!(fibonacci(485364503, 443474572, 571864946) == 958752252);
-        assert!(fibonacci(63913312, 294792466, 86667906) == 10105258);
-        assert!(fibonacci(617183514, 726951605, 241070512) == 325261340);
-        assert!(fibonacci(487424245, 972909803, 844677746) == 878251165);
-        assert!(fibonacci(876282766, 228514011, 608421581) == 212247381);
-        assert!(fibonacci(342418065, 483321675, 999941452) == 187986765);
-        assert!(fibonacci(144534785, 327403517, 318763484) == 215675834);
-        assert!(fibonacci(949168740, 877100430, 877727535) == 132146650);
-        assert!(fibonacci(24991506, 975890684, 622868602) == 535258848);
-        assert!(fibonacci(444529524, 959123856, 608186887) == 221652891);
-        assert!(fibonacci(751450020, 839202303, 543839125) == 725210032);
-        assert!(fibonacci(547448306, 241221454, 542086802) == 659956667);
-        assert!(fibonacci(176749709, 592043735, 108062633) == 228612491);
-        assert!(fibonacci(767928520, 781082922, 377141563) == 357082225);
-        assert!(fibonacci(735574036, 843677602, 65996036) == 984005252);
-        assert!(fibonacci(57512868, 481816987, 485820787) == 112577658);
-        assert!(fibonacci(48979313, 37562505, 161149401) == 599617041);
-        assert!(fibonacci(926565765, 612955181, 524854290) == 936910309);
-        assert!(fibonacci(424933063, 536195762, 566374141) == 72775083);
-        assert!(fibonacci(694137410, 38921806, 318901782) == 197875980);
-        assert!(fibonacci(463799304, 172653991, 767827749) == 268150069);
-        assert!(fibonacci(632883624, 735974224, 133707500) == 350882793);
-        assert!(fibonacci(300838621, 328834461, 987292557) == 752283839);
-        assert!(fibonacci(66334779, 165668356, 891277940) == 320714095);
-        assert!(fibonacci(891878820, 376606265, 546320595) == 276436314);
-        assert!(fibonacci(941621921, 213594704, 33422829) == 700489863);
-        assert!(fibonacci(811731500, 967478638, 494073697) == 626720364);
-        assert!(fibonacci(137047816, 395550211, 921561352) == 108790229);
-        assert!(fibonacci(447037486, 824884255, 226247123) == 547691569);
-        assert!(fibonacci(18984836, 122515243, 705025692) == 383360651);
-        assert!(fibonacci(975712660, 482612299, 553121929) == 864789935);
-        assert!(fibonacci(789160851, 304129217, 608428932) == 821695434);
-        assert!(fibonacci(609313813, 102690413, 107703378) == 846047335);
-        assert!(fibonacci(932288535, 756674246, 152982526) == 285471481);
-        assert!(fibonacci(498528230, 982644534, 871894148) == 302633489);
-        assert!(fibonacci(269871461, 34686946, 236348917) == 531105411);
-        assert!(fibonacci(978533695, 483311142, 322601058) == 608558680);
-        assert!(fibonacci(532951313, 882405910, 71443168) == 673251743);
-        assert!(fibonacci(824828084, 326751489, 777103779) == 994290335);
-        assert!(fibonacci(846266397, 185846139, 911089969) == 426610799);
-        assert!(fibonacci(907460377, 281853786, 206319218) == 774709597);
-        assert!(fibonacci(282266865, 90622118, 683056057) == 70873410);
-        assert!(fibonacci(338156011, 102717970, 72477049) == 55862296);
-        assert!(fibonacci(937891662, 332308108, 110904292) == 246277205);
-        assert!(fibonacci(925719450, 879442964, 210804393) == 31267297);
-        assert!(fibonacci(341091171, 638519585, 412905677) == 429052778);
-        assert!(fibonacci(49667204, 177166026, 790197309) == 570534174);
-        assert!(fibonacci(989898206, 40451140, 639204101) == 728181676);
-        assert!(fibonacci(382424634, 442761348, 600206389) == 186386798);
-        assert!(fibonacci(481678762, 964332766, 977377728) == 61135834);
-        assert!(fibonacci(831325152, 763047416, 916844273) == 457489026);
-        assert!(fibonacci(196613409, 24828158, 474820460) == 949101375);
-        assert!(fibonacci(946663307, 216261365, 892022416) == 752803553);
-        assert!(fibonacci(188838092, 931715449, 931968976) == 108708806);
-        assert!(fibonacci(163871868, 40642033, 151003034) == 148045961);
-        assert!(fibonacci(211696766, 607231862, 2962946) == 239708353);
-        assert!(fibonacci(860638775, 483736913, 688231903) == 881465976);
-        assert!(fibonacci(364198102, 796599194, 8680113) == 126152049);
-        assert!(fibonacci(120892661, 917562171, 241402646) == 487583358);
-        assert!(fibonacci(567115117, 899842231, 647860770) == 159552643);
-        assert!(fibonacci(82849668, 856477249, 138385203) == 731746987);
-        assert!(fibonacci(95099321, 106566510, 569260691) == 871149505);
-        assert!(fibonacci(194060018, 729106042, 854614806) == 229932044);
-        assert!(fibonacci(735983932, 394427863, 422258220) == 602353821);
-        assert!(fibonacci(520883261, 860829111, 245450577) == 743260614);
-        assert!(fibonacci(858228332, 826026338, 999809106) == 121576980);
-        assert!(fibonacci(488201068, 170727869, 835529485) == 961409580);
-        assert!(fibonacci(597243443, 621039987, 480595853) == 61744649);
-        assert!(fibonacci(289145825, 467127051, 966448222) == 919023230);
-        assert!(fibonacci(908164161, 75720749, 107265987) == 492221317);
-        assert!(fibonacci(233417815, 379548191, 316419134) == 883245404);
-        assert!(fibonacci(244161647, 105988424, 75238201) == 787572158);
-        assert!(fibonacci(793583774, 68772548, 934075523) == 310569887);
-        assert!(fibonacci(581742342, 17636749, 878125820) == 16657987);
-        assert!(fibonacci(148348139, 124654843, 89309051) == 400653541);
-        assert!(fibonacci(265973684, 843314841, 935770082) == 492896452);
-        assert!(fibonacci(763229536, 262664328, 91535834) == 230195943);
-        assert!(fibonacci(241593540, 78914050, 729114363) == 456031719);
-        assert!(fibonacci(812331284, 485606225, 235437262) == 904861753);
-        assert!(fibonacci(657789350, 805953495, 639439459) == 747645245);
-        assert!(fibonacci(602812469, 285895857, 653385293) == 183231445);
-        assert!(fibonacci(747348820, 502566118, 14879672) == 54736548);
-        assert!(fibonacci(105839086, 276721614, 754840687) == 722439666);
-        assert!(fibonacci(170212376, 64644559, 757253790) == 384251576);
-        assert!(fibonacci(65733584, 906458725, 153091120) == 104403979);
-        assert!(fibonacci(957403665, 448895305, 496385144) == 205238593);
-        assert!(fibonacci(391286803, 23172142, 623812209) == 218751382);
-        assert!(fibonacci(30678140, 144934625, 813056420) == 36361364);
-        assert!(fibonacci(15730154, 991824274, 300634142) == 483784272);
-        assert!(fibonacci(587304771, 431830362, 620382199) == 686878760);
-        assert!(fibonacci(839800939, 135794424, 262819053) == 215643036);
-        assert!(fibonacci(294636064, 978929873, 731740891) == 222268437);
-        assert!(fibonacci(224740527, 400999319, 622088725) == 165479532);
-        assert!(fibonacci(74429379, 160134598, 566593228) == 425477692);
-        assert!(fibonacci(180929395, 593017160, 198019770) == 641396461);
-        assert!(fibonacci(334282043, 175859956, 172455324) == 824656796);
-        assert!(fibonacci(576023243, 854927619, 36710920) == 755412502);
-        assert!(fibonacci(739052117, 266502547, 782781354) == 983716005);
-        assert!(fibonacci(15627251, 572134329, 140961232) == 830453841);
-        assert!(fibonacci(840328873, 731618667, 510741843) == 795980828);
-        assert!(fibonacci(714245529, 518534315, 844891252) == 394126166);
-        assert!(fibonacci(134458489, 330411437, 928850134) == 586902230);
-        assert!(fibonacci(494434738, 771097520, 927528852) == 65226943);
-        assert!(fibonacci(421350229, 732663138, 47895987) == 545021227);
-        assert!(fibonacci(874296418, 316857424, 863037543) == 483748976);
-        assert!(fibonacci(57218981, 849748745, 551917465) == 904338270);
-        assert!(fibonacci(615036651, 681448110, 815964319) == 643338159);
-        assert!(fibonacci(128178819, 11281529, 576902743) == 598657929);
-        assert!(fibonacci(583671279, 100748822, 869291876) == 92726057);
-        assert!(fibonacci(480037698, 279008814, 406795544) == 164161399);
-        assert!(fibonacci(590779660, 384411654, 669380651) == 970748447);
-        assert!(fibonacci(111346883, 360183849, 641600283) == 96675862);
-        assert!(fibonacci(721701976, 46470907, 262605723) == 978015180);
-        assert!(fibonacci(269169635, 839847305, 404762436) == 573435506);
-        assert!(fibonacci(244926472, 154673014, 122264481) == 75518598);
-        assert!(fibonacci(988814933, 864755698, 949645123) == 149946896);
-        assert!(fibonacci(919743811, 958408269, 722385584) == 740952209);
-        assert!(fibonacci(589043767, 225292222, 50170557) == 416794865);
-        assert!(fibonacci(694777523, 586066711, 507252786) == 712109115);
-        assert!(fibonacci(267988509, 550787209, 229662616) == 583901397);
-        assert!(fibonacci(59558258, 14397041, 492088706) == 108500732);
-        assert!(fibonacci(520733308, 830570569, 348251485) == 853347687);
-        assert!(fibonacci(378515335, 762949536, 956673575) == 207429122);
-        assert!(fibonacci(221437260, 335517005, 803277839) == 657210050);
-        assert!(fibonacci(289311742, 345867016, 841600805) == 659077570);
-        assert!(fibonacci(411201883, 883252346, 856608514) == 134676369);
-        assert!(fibonacci(454638263, 594856346, 235993124) == 36585534);
-        assert!(fibonacci(919646753, 560293887, 320600544) == 201276985);
-        assert!(fibonacci(684409960, 1735893, 159119432) == 639908659);
-        assert!(fibonacci(619210095, 416569359, 774117137) == 945381138);
-        assert!(fibonacci(134347497, 453713467, 495683697) == 338731816);
-        assert!(fibonacci(32943108, 209611378, 825450265) == 188646117);
-        assert!(fibonacci(912673729, 724193163, 324102445) == 680398766);
-        assert!(fibonacci(774012995, 610299599, 101806163) == 589148217);
-        assert!(fibonacci(992971547, 698306551, 622891264) == 510030456);
-        assert!(fibonacci(919107744, 299732026, 879425206) == 892425798);
-        assert!(fibonacci(208569752, 283575640, 702814365) == 337869347);
-        assert!(fibonacci(650644271, 813350245, 955930863) == 688435634);
-        assert!(fibonacci(993669492, 139911504, 454103153) == 653963751);
-        assert!(fibonacci(171488163, 836323348, 828834676) == 822304721);
-        assert!(fibonacci(189132054, 759305239, 346380177) == 630698013);
-        assert!(fibonacci(182556439, 87089763, 881803538) == 122173570);
-        assert!(fibonacci(307594142, 256368634, 136255638) == 982354338);
-        assert!(fibonacci(16150540, 498528154, 159059183) == 997565962);
-        assert!(fibonacci(532506070, 680625267, 984556746) == 593069442);
-        assert!(fibonacci(134186961, 926675206, 861987335) == 74678762);
-        assert!(fibonacci(697709279, 765302215, 702003867) == 22388650);
-        assert!(fibonacci(279694225, 410640343, 132993720) == 350106572);
-        assert!(fibonacci(71302772, 483703226, 640363221) == 578342015);
-        assert!(fibonacci(539752834, 39273616, 69699875) == 721880171);
-        assert!(fibonacci(371347112, 741185567, 887869815) == 59092205);
-        assert!(fibonacci(495267768, 584880942, 850994359) == 451244008);
-        assert!(fibonacci(755425986, 810415109, 611216788) == 744870757);
-        assert!(fibonacci(741087726, 611513602, 43363392) == 524729602);
-        assert!(fibonacci(743169569, 192419212, 785047486) == 348509849);
-        assert!(fibonacci(543755182, 118138202, 132724978) == 546306614);
-        assert!(fibonacci(971374116, 859482531, 213236297) == 306383797);
-        assert!(fibonacci(592115818, 473778883, 71021133) == 239647003);
-        assert!(fibonacci(126830810, 909437828, 348664896) == 472042390);
-        assert!(fibonacci(213386458, 111253667, 603386537) == 358141802);
-        assert!(fibonacci(241440317, 767841308, 217095019) == 407998994);
-        assert!(fibonacci(66555764, 644803428, 757342587) == 112289059);
-        assert!(fibonacci(271189368, 37238302, 95744326) == 893128901);
-        assert!(fibonacci(133562387, 378760975, 116260098) == 280083883);
-        assert!(fibonacci(250770548, 956621553, 153788614) == 567898950);
-        assert!(fibonacci(658640476, 536524656, 218221132) == 449923220);
-        assert!(fibonacci(347946233, 527547590, 365565024) == 327348870);
-        assert!(fibonacci(507638243, 568378718, 179791084) == 843845069);
-        assert!(fibonacci(856463577, 779231294, 267406684) == 872753431);
-        assert!(fibonacci(816848682, 368436959, 675443113) == 541339239);
-        assert!(fibonacci(502329463, 542039528, 699161443) == 434891149);
-        assert!(fibonacci(7830251, 499647409, 843672294) == 14467185);
-        assert!(fibonacci(826268373, 676613805, 547615784) == 393767024);
-        assert!(fibonacci(27704900, 272565815, 80899900) == 704223904);
-        assert!(fibonacci(36980653, 837811729, 480721556) == 365504307);
-        assert!(fibonacci(96976200, 341345270, 517157330) == 905164148);
-        assert!(fibonacci(917232519, 468190334, 372913173) == 524117004);
-        assert!(fibonacci(130443765, 865440224, 583706076) == 853152402);
-        assert!(fibonacci(237821514, 733802073, 199462590) == 856510885);
-        assert!(fibonacci(37303935, 210092187, 865572134) == 64850097);
-        assert!(fibonacci(489935904, 454340685, 668801343) == 876798154);
-        assert!(fibonacci(420301213, 631232924, 836721446) == 63273913);
-        assert!(fibonacci(417098655, 242940297, 456974144) == 378502002);
-        assert!(fibonacci(310116319, 832372102, 859910850) == 242320680);
-        assert!(fibonacci(670605347, 320241334, 254981775) == 578584957);
-        assert!(fibonacci(284665124, 470657591, 730566988) == 342611396);
-        assert!(fibonacci(699069528, 759487377, 437886024) == 757218455);
-        assert!(fibonacci(750249353, 899616163, 900246625) == 144379526);
-        assert!(fibonacci(302529941, 371885699, 676599303) == 742065465);
-        assert!(fibonacci(115455367, 770008737, 765845336) == 974831098);
-        assert!(fibonacci(644209705, 788964438, 466521619) == 463229150);
-        assert!(fibonacci(682043650, 537768996, 818225130) == 283036520);
-        assert!(fibonacci(412098557, 616679439, 206578806) == 52463664);
-        assert!(fibonacci(644000109, 679877545, 98404974) == 226640133);
-        assert!(fibonacci(60183187, 607116201, 635818231) == 508402640);
-        assert!(fibonacci(513002322, 459838418, 545198891) == 679790687);
-        assert!(fibonacci(328724302, 953156389, 263144483) == 821566178);
-        assert!(fibonacci(468895601, 338234407, 450604809) == 19773473);
-        assert!(fibonacci(427686110, 739686550, 554724522) == 999032240);
-        assert!(fibonacci(768554717, 117771273, 259347225) == 52840861);
-        assert!(fibonacci(160122144, 301643289, 472931560) == 346380126);
-        assert!(fibonacci(691128808, 665906614, 43407665) == 936872129);
-        assert!(fibonacci(393389232, 988561697, 782472338) == 458823828);
-        assert!(fibonacci(436756645, 872566567, 853978151) == 779946528);
-        assert!(fibonacci(523988183, 55069419, 79609833) == 435926258);
-        assert!(fibonacci(339481050, 690066167, 292499963) == 454883869);
-        assert!(fibonacci(264428395, 789527618, 912047280) == 744410738);
-        assert!(fibonacci(131400413, 786731064, 816852348) == 260316408);
-        assert!(fibonacci(502700853, 175314104, 450730032) == 456741492);
-        assert!(fibonacci(15916810, 254357540, 798082445) == 192525894);
-        assert!(fibonacci(263500608, 643543839, 498908167) == 171827889);
-        assert!(fibonacci(947231580, 483877966, 378234257) == 526905059);
-        assert!(fibonacci(625473413, 369054378, 941209586) == 318061227);
-        assert!(fibonacci(592410566, 119613090, 471276720) == 652938061);
-        assert!(fibonacci(762726553, 891967656, 80589822) == 208436485);
-        assert!(fibonacci(409166331, 944907418, 283164989) == 811431188);
-        assert!(fibonacci(370994977, 88205060, 49620383) == 502663211);
-        assert!(fibonacci(262224560, 504126323, 328243654) == 471046761);
-        assert!(fibonacci(347299944, 28721731, 514912224) == 518089205);
-        assert!(fibonacci(656079212, 188427955, 490592511) == 973739307);
-        assert!(fibonacci(592677632, 377472423, 734204157) == 955985642);
-        assert!(fibonacci(984389892, 173256037, 229022728) == 179590453);
-        assert!(fibonacci(355069984, 576756602, 571191452) == 732519729);
-        assert!(fibonacci(701375575, 714007637, 321011789) == 406924718);
-        assert!(fibonacci(351290376, 672017828, 669914529) == 786245692);
-        assert!(fibonacci(440770561, 368673859, 894763408) == 166784978);
-        assert!(fibonacci(736262526, 93534523, 230406685) == 879179613);
-        assert!(fibonacci(167565044, 644921832, 166152480) == 77383285);
-        assert!(fibonacci(748462063, 1276049, 139417516) == 744124052);
-        assert!(fibonacci(170664514, 599931636, 455156236) == 255173024);
-        assert!(fibonacci(863322033, 969394201, 180626424) == 46721496);
-        assert!(fibonacci(450617076, 999732934, 742140667) == 801509114);
-        assert!(fibonacci(737072562, 778336661, 718711619) == 805139494);
-        assert!(fibonacci(851567094, 54096348, 368150816) == 646200987);
-        assert!(fibonacci(711973536, 669619401, 374197423) == 981360533);
-        assert!(fibonacci(728608593, 910934183, 832108495) == 81219513);
-        assert!(fibonacci(658329124, 906529383, 660047871) == 238032796);
-        assert!(fibonacci(620148815, 919816686, 94893433) == 274150383);
-        assert!(fibonacci(260185826, 425112589, 732550590) == 887890464);
-        assert!(fibonacci(568051678, 235927829, 171979988) == 206315361);
-        assert!(fibonacci(89605212, 184405470, 976824966) == 804075875);
-        assert!(fibonacci(116035216, 838053542, 744613435) == 361860816);
-        assert!(fibonacci(140385366, 900673299, 672284893) == 946247853);
-        assert!(fibonacci(927773862, 703697998, 590337198) == 297006980);
-        assert!(fibonacci(176051789, 884695431, 39438176) == 579011536);
-        assert!(fibonacci(862255869, 455591507, 975302431) == 290218594);
-        assert!(fibonacci(791955056, 19853470, 90341866) == 671275084);
-        assert!(fibonacci(307309021, 512335390, 693402252) == 703408997);
-        assert!(fibonacci(795108364, 243173218, 49577516) == 421937854);
-        assert!(fibonacci(85732992, 190431251, 574620346) == 296150427);
-        assert!(fibonacci(9590077, 169020885, 542408832) == 192725356);
-        assert!(fibonacci(546731631, 761961882, 869690877) == 450539467);
-        assert!(fibonacci(391325850, 595938320, 831565858) == 375724693);
-        assert!(fibonacci(629583988, 588223227, 10260885) == 392179391);
-        assert!(fibonacci(159823602, 730555262, 202831386) == 354286019);
-        assert!(fibonacci(69710673, 444097027, 35121255) == 791924818);
-        assert!(fibonacci(55455258, 397929957, 969521081) == 52321305);
-        assert!(fibonacci(757248159, 776096621, 216074360) == 451752148);
-        assert!(fibonacci(81638123, 782296191, 672070080) == 293731844);
-        assert!(fibonacci(283422714, 320926852, 594880325) == 572015683);
-        assert!(fibonacci(695035920, 54652375, 710242049) == 176269304);
-        assert!(fibonacci(97664548, 918183785, 534677113) == 235557854);
-        assert!(fibonacci(47928367, 629320157, 122733550) == 248470518);
-        assert!(fibonacci(169747298, 470930019, 859653186) == 737311477);
-        assert!(fibonacci(143736573, 162371069, 431630242) == 768822504);
-        assert!(fibonacci(80080981, 147485420, 781780127) == 933623198);
-        assert!(fibonacci(490570867, 725397691, 799076190) == 852081659);
-        assert!(fibonacci(14456261, 30277735, 792501293) == 260337802);
-        assert!(fibonacci(605099264, 252341918, 392924263) == 974187462);
-        assert!(fibonacci(326334473, 465093509, 979665691) == 9645521);
-        assert!(fibonacci(197620797, 107903136, 275011469) == 771973108);
-        assert!(fibonacci(236685534, 934548067, 533570853) == 144229618);
-        assert!(fibonacci(300018835, 62159054, 812371477) == 814311468);
-        assert!(fibonacci(668154272, 21782307, 814776870) == 238977376);
-        assert!(fibonacci(912387705, 885707959, 926999937) == 976549515);
-        assert!(fibonacci(109867895, 613511586, 613725553) == 803909667);
-        assert!(fibonacci(784444117, 1557143, 634810771) == 51306082);
-        assert!(fibonacci(890289953, 792851847, 595529160) == 882831959);
-        assert!(fibonacci(795804134, 680579660, 24374641) == 321107678);
-        assert!(fibonacci(917740755, 492565283, 665841867) == 752770771);
-        assert!(fibonacci(141752850, 721593894, 164226649) == 220868660);
-        assert!(fibonacci(143067858, 972126500, 363026883) == 499747309);
-        assert!(fibonacci(584641718, 907051038, 227823002) == 492653026);
-        assert!(fibonacci(898059389, 780062146, 85326366) == 477020225);
-        assert!(fibonacci(969408650, 822416594, 703615385) == 533849974);
-        assert!(fibonacci(984299320, 102068556, 144569775) == 392714630);
-        assert!(fibonacci(865501355, 495231369, 638830331) == 652421523);
-        assert!(fibonacci(779198710, 541890356, 463536367) == 912451785);
-        assert!(fibonacci(344854920, 307330844, 315051289) == 657897446);
-        assert!(fibonacci(679482308, 440881399, 253968408) == 853116369);
-        assert!(fibonacci(509196213, 382278079, 569593771) == 663771295);
-        assert!(fibonacci(146529610, 526401423, 300188412) == 8831417);
-        assert!(fibonacci(650550221, 769319847, 913581458) == 883564770);
-        assert!(fibonacci(563973054, 525226177, 706460547) == 796580832);
-        assert!(fibonacci(385902551, 886384727, 221494996) == 724057423);
-    }
 }
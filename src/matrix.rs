@@ -0,0 +1,528 @@
+//! Matrices over any `Semiring`, which covers the ordinary number types as
+//! well as the min-plus, max-plus and boolean semirings for path problems.
+//!
+//! `Matrix` decides its shape at run time and checks it on every operation:
+//! the `checked_*` methods return a `ShapeError`, and the operators panic
+//! with one. `SMatrix` carries its shape in its type, so mismatches don't
+//! compile and small matrices stay on the stack.
+
+use std::{array, mem};
+use std::error;
+use std::fmt::{Display, Error, Formatter};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+
+use ring::{Ring, Semiring};
+
+/// Matrices, or a matrix and its elements, whose shapes don't fit together.
+/// Shapes are `(rows, cols)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeError {
+    /// An operation between two matrices of incompatible shapes.
+    Mismatch { op: &'static str, lhs: (usize, usize), rhs: (usize, usize) },
+    /// An operation that needs a square matrix.
+    NotSquare { op: &'static str, shape: (usize, usize) },
+    /// Elements that don't fill a matrix, or a row, exactly.
+    Length { expected: usize, len: usize },
+}
+
+impl Display for ShapeError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match *self {
+            ShapeError::Mismatch { op, lhs, rhs } => {
+                write!(f, "can't {} a {}x{} matrix and a {}x{} matrix", op, lhs.0, lhs.1, rhs.0, rhs.1)
+            }
+            ShapeError::NotSquare { op, shape } => {
+                write!(f, "can't {} a {}x{} matrix, it isn't square", op, shape.0, shape.1)
+            }
+            ShapeError::Length { expected, len } => {
+                write!(f, "expected {} elements, got {}", expected, len)
+            }
+        }
+    }
+}
+
+impl error::Error for ShapeError {}
+
+/// A matrix with its shape decided at run time, stored row-major in one
+/// `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T> Matrix<T> {
+    /// A `rows` by `cols` matrix of `data`, in row-major order.
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Result<Matrix<T>, ShapeError> {
+        if data.len() != rows * cols {
+            return Err(ShapeError::Length { expected: rows * cols, len: data.len() });
+        }
+        Ok(Matrix { rows, cols, data })
+    }
+
+    /// A matrix from its rows, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Matrix<T>, ShapeError> {
+        let cols = rows.first().map_or(0, Vec::len);
+        let mut data = Vec::with_capacity(rows.len() * cols);
+        let height = rows.len();
+        for row in rows {
+            if row.len() != cols {
+                return Err(ShapeError::Length { expected: cols, len: row.len() });
+            }
+            data.extend(row);
+        }
+        Ok(Matrix { rows: height, cols, data })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// `(rows, cols)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// The elements in row-major order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+}
+
+impl<T: Semiring> Matrix<T> {
+    pub fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix { rows, cols, data: vec![T::zero(); rows * cols] }
+    }
+
+    pub fn identity(n: usize) -> Matrix<T> {
+        let mut m = Matrix::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = T::one();
+        }
+        m
+    }
+
+    pub fn transpose(&self) -> Matrix<T> {
+        let mut data = Vec::with_capacity(self.data.len());
+        for j in 0..self.cols {
+            data.extend((0..self.rows).map(|i| self[(i, j)].clone()));
+        }
+        Matrix { rows: self.cols, cols: self.rows, data }
+    }
+
+    pub fn checked_add(&self, rhs: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        self.zip_with("add", rhs, |a, b| a.plus(b))
+    }
+
+    /// The matrix product, if `self` has as many columns as `rhs` has rows.
+    pub fn checked_mul(&self, rhs: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        if self.cols != rhs.rows {
+            return Err(ShapeError::Mismatch { op: "multiply", lhs: self.shape(), rhs: rhs.shape() });
+        }
+        let mut out = Matrix { rows: 0, cols: 0, data: Vec::with_capacity(self.rows * rhs.cols) };
+        self.mul_into(rhs, &mut out);
+        Ok(out)
+    }
+
+    /// Overwrite `out` with `self * rhs`, reusing its storage. The shapes
+    /// must already have been checked.
+    fn mul_into(&self, rhs: &Matrix<T>, out: &mut Matrix<T>) {
+        out.rows = self.rows;
+        out.cols = rhs.cols;
+        out.data.clear();
+        for i in 0..self.rows {
+            let row = self.row(i);
+            out.data.extend((0..rhs.cols).map(|j| {
+                row.iter().enumerate().fold(T::zero(), |acc, (k, a)| acc.plus(&a.times(&rhs[(k, j)])))
+            }));
+        }
+    }
+
+    /// `self` multiplied by itself `exp` times by repeated squaring; the
+    /// identity for `exp == 0`. Allocates three matrices up front and none
+    /// after that.
+    pub fn pow(&self, mut exp: u64) -> Result<Matrix<T>, ShapeError> {
+        if !self.is_square() {
+            return Err(ShapeError::NotSquare { op: "exponentiate", shape: self.shape() });
+        }
+        let mut acc = Matrix::identity(self.rows);
+        let mut base = self.clone();
+        let mut scratch = Matrix { rows: 0, cols: 0, data: Vec::with_capacity(self.data.len()) };
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc.mul_into(&base, &mut scratch);
+                mem::swap(&mut acc, &mut scratch);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base.mul_into(&base, &mut scratch);
+                mem::swap(&mut base, &mut scratch);
+            }
+        }
+        Ok(acc)
+    }
+
+    fn zip_with<F>(&self, op: &'static str, rhs: &Matrix<T>, f: F) -> Result<Matrix<T>, ShapeError>
+        where F: Fn(&T, &T) -> T
+    {
+        if self.shape() != rhs.shape() {
+            return Err(ShapeError::Mismatch { op, lhs: self.shape(), rhs: rhs.shape() });
+        }
+        let data = self.data.iter().zip(&rhs.data).map(|(a, b)| f(a, b)).collect();
+        Ok(Matrix { rows: self.rows, cols: self.cols, data })
+    }
+}
+
+impl<T: Ring> Matrix<T> {
+    pub fn checked_sub(&self, rhs: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        self.zip_with("subtract", rhs, |a, b| a.clone() - b.clone())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(j < self.cols, "column {} out of range for a {}x{} matrix", j, self.rows, self.cols);
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(j < self.cols, "column {} out of range for a {}x{} matrix", j, self.rows, self.cols);
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+/// One row per line, elements separated by spaces.
+impl<T: Display> Display for Matrix<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        for i in 0..self.rows {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            for (j, x) in self.row(i).iter().enumerate() {
+                if j > 0 {
+                    f.write_str(" ")?;
+                }
+                x.fmt(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+macro_rules! impl_checked_op {
+    ($bound:ident: $imp:ident, $method:ident, $assign_imp:ident, $assign_method:ident, $checked:ident) => {
+        /// Panics if the shapes don't fit; see the `checked_` form.
+        impl<T: $bound> $imp<&Matrix<T>> for &Matrix<T> {
+            type Output = Matrix<T>;
+
+            fn $method(self, rhs: &Matrix<T>) -> Matrix<T> {
+                self.$checked(rhs).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        forward_binop!(impl[T: $bound] Matrix<T>: $imp, $method, $assign_imp, $assign_method);
+    };
+}
+
+impl_checked_op!(Semiring: Add, add, AddAssign, add_assign, checked_add);
+impl_checked_op!(Ring: Sub, sub, SubAssign, sub_assign, checked_sub);
+impl_checked_op!(Semiring: Mul, mul, MulAssign, mul_assign, checked_mul);
+
+/// An `R` by `C` matrix with its shape in its type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SMatrix<T, const R: usize, const C: usize>([[T; C]; R]);
+
+impl<T, const R: usize, const C: usize> SMatrix<T, R, C> {
+    pub fn new(rows: [[T; C]; R]) -> SMatrix<T, R, C> {
+        SMatrix(rows)
+    }
+
+    pub fn into_rows(self) -> [[T; C]; R] {
+        self.0
+    }
+}
+
+impl<T: Semiring, const R: usize, const C: usize> SMatrix<T, R, C> {
+    pub fn zeros() -> SMatrix<T, R, C> {
+        SMatrix(array::from_fn(|_| array::from_fn(|_| T::zero())))
+    }
+
+    pub fn transpose(&self) -> SMatrix<T, C, R> {
+        SMatrix(array::from_fn(|j| array::from_fn(|i| self.0[i][j].clone())))
+    }
+}
+
+impl<T: Semiring, const N: usize> SMatrix<T, N, N> {
+    pub fn identity() -> SMatrix<T, N, N> {
+        SMatrix(array::from_fn(|i| array::from_fn(|j| if i == j { T::one() } else { T::zero() })))
+    }
+
+    /// `self` multiplied by itself `exp` times; the identity for `exp == 0`.
+    pub fn pow(&self, mut exp: u64) -> SMatrix<T, N, N> {
+        let mut acc = SMatrix::identity();
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = &acc * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        acc
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for SMatrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.0[i][j]
+    }
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for SMatrix<T, R, C> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.0[i][j]
+    }
+}
+
+impl<T, const R: usize, const C: usize> From<SMatrix<T, R, C>> for Matrix<T> {
+    fn from(m: SMatrix<T, R, C>) -> Matrix<T> {
+        Matrix { rows: R, cols: C, data: IntoIterator::into_iter(m.0).flat_map(IntoIterator::into_iter).collect() }
+    }
+}
+
+impl<T: Semiring, const R: usize, const C: usize, const K: usize> Mul<&SMatrix<T, C, K>> for &SMatrix<T, R, C> {
+    type Output = SMatrix<T, R, K>;
+
+    fn mul(self, rhs: &SMatrix<T, C, K>) -> SMatrix<T, R, K> {
+        SMatrix(array::from_fn(|i| array::from_fn(|j| {
+            (0..C).fold(T::zero(), |acc, k| acc.plus(&self.0[i][k].times(&rhs.0[k][j])))
+        })))
+    }
+}
+
+impl<T: Semiring, const R: usize, const C: usize, const K: usize> Mul<SMatrix<T, C, K>> for SMatrix<T, R, C> {
+    type Output = SMatrix<T, R, K>;
+
+    fn mul(self, rhs: SMatrix<T, C, K>) -> SMatrix<T, R, K> {
+        &self * &rhs
+    }
+}
+
+impl<T: Semiring, const R: usize, const C: usize> Add<&SMatrix<T, R, C>> for &SMatrix<T, R, C> {
+    type Output = SMatrix<T, R, C>;
+
+    fn add(self, rhs: &SMatrix<T, R, C>) -> SMatrix<T, R, C> {
+        SMatrix(array::from_fn(|i| array::from_fn(|j| self.0[i][j].plus(&rhs.0[i][j]))))
+    }
+}
+
+impl<T: Ring, const R: usize, const C: usize> Sub<&SMatrix<T, R, C>> for &SMatrix<T, R, C> {
+    type Output = SMatrix<T, R, C>;
+
+    fn sub(self, rhs: &SMatrix<T, R, C>) -> SMatrix<T, R, C> {
+        SMatrix(array::from_fn(|i| array::from_fn(|j| self.0[i][j].clone() - rhs.0[i][j].clone())))
+    }
+}
+
+forward_binop!(impl[T: Semiring, const R: usize, const C: usize] SMatrix<T, R, C>: Add, add, AddAssign, add_assign);
+forward_binop!(impl[T: Ring, const R: usize, const C: usize] SMatrix<T, R, C>: Sub, sub, SubAssign, sub_assign);
+
+#[cfg(test)]
+mod tests {
+    use super::{Matrix, SMatrix, ShapeError};
+    use bigint::BigInt;
+    use modint::ModInt;
+    use ring::{MaxPlus, MinPlus, Semiring};
+    use test::Bencher;
+    use test_support::next_random;
+
+    fn matrix(rows: Vec<Vec<i64>>) -> Matrix<i64> {
+        Matrix::from_rows(rows).unwrap()
+    }
+
+    /// Pseudo-random `rows` by `cols` matrices with small entries.
+    fn random(seed: &mut u64, rows: usize, cols: usize) -> Matrix<i64> {
+        let data = (0..rows * cols).map(|_| {
+            (next_random(seed) >> 60) as i64 - 8
+        });
+        Matrix::from_vec(rows, cols, data.collect()).unwrap()
+    }
+
+    #[test]
+    fn test_construction_and_shape_errors() {
+        let a = matrix(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!((a.rows(), a.cols(), a.shape()), (2, 3, (2, 3)));
+        assert_eq!(a[(1, 0)], 4);
+        assert_eq!(a.row(1), &[4, 5, 6]);
+        assert_eq!(a.as_slice(), &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(a.transpose(), matrix(vec![vec![1, 4], vec![2, 5], vec![3, 6]]));
+        assert_eq!(a.to_string(), "1 2 3\n4 5 6");
+
+        assert_eq!(Matrix::from_rows(vec![vec![1, 2], vec![3]]),
+                   Err(ShapeError::Length { expected: 2, len: 1 }));
+        assert_eq!(Matrix::from_vec(2, 2, vec![1, 2, 3]),
+                   Err(ShapeError::Length { expected: 4, len: 3 }));
+
+        let err = a.checked_mul(&a).unwrap_err();
+        assert_eq!(err, ShapeError::Mismatch { op: "multiply", lhs: (2, 3), rhs: (2, 3) });
+        assert_eq!(err.to_string(), "can't multiply a 2x3 matrix and a 2x3 matrix");
+        assert!(a.checked_add(&a.transpose()).is_err());
+        assert_eq!(a.pow(2), Err(ShapeError::NotSquare { op: "exponentiate", shape: (2, 3) }));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't add a 2x3 matrix and a 3x2 matrix")]
+    fn test_operator_shape_mismatch() {
+        let a = Matrix::<i64>::zeros(2, 3);
+        let _ = &a + &a.transpose();
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = matrix(vec![vec![1, 2], vec![3, 4]]);
+        let b = matrix(vec![vec![5, 6], vec![7, 8]]);
+        assert_eq!(&a * &b, matrix(vec![vec![19, 22], vec![43, 50]]));
+        assert_eq!(&a + &b, matrix(vec![vec![6, 8], vec![10, 12]]));
+        assert_eq!(b.clone() - a.clone(), matrix(vec![vec![4, 4], vec![4, 4]]));
+
+        let mut seed = 1;
+        for _ in 0..20 {
+            let x = random(&mut seed, 3, 4);
+            let y = random(&mut seed, 4, 2);
+            let z = random(&mut seed, 2, 5);
+            assert_eq!((&x * &y) * &z, &x * (&y * &z));
+            assert_eq!((&x * &y).transpose(), y.transpose() * x.transpose());
+            assert_eq!(&x * Matrix::identity(4), x);
+        }
+    }
+
+    #[test]
+    fn test_pow() {
+        let fib = matrix(vec![vec![1, 1], vec![1, 0]]);
+        assert_eq!(fib.pow(0).unwrap(), Matrix::identity(2));
+        assert_eq!(fib.pow(7).unwrap(), matrix(vec![vec![21, 13], vec![13, 8]]));
+
+        let mut seed = 7;
+        let m = random(&mut seed, 3, 3);
+        let mut acc = Matrix::identity(3);
+        for n in 0..10 {
+            assert_eq!(m.pow(n).unwrap(), acc);
+            acc *= &m;
+        }
+
+        // F(300) overflows every primitive.
+        let big: Matrix<BigInt> = Matrix::from_rows(vec![
+            vec![BigInt::one(), BigInt::one()],
+            vec![BigInt::one(), BigInt::zero()],
+        ]).unwrap();
+        assert_eq!(big.pow(300).unwrap()[(0, 1)].to_string(),
+                   "222232244629420445529739893461909967206666939096499764990979600");
+    }
+
+    #[test]
+    fn test_smatrix() {
+        let fib = SMatrix::new([[1i64, 1], [1, 0]]);
+        assert_eq!(fib.pow(0), SMatrix::identity());
+        assert_eq!(fib.pow(7).into_rows(), [[21, 13], [13, 8]]);
+        assert_eq!(Matrix::from(fib.pow(20)), matrix(vec![vec![1, 1], vec![1, 0]]).pow(20).unwrap());
+
+        let a = SMatrix::new([[1i64, 2, 3], [4, 5, 6]]);
+        let column = SMatrix::new([[1], [0], [-1]]);
+        assert_eq!((a * column).into_rows(), [[-2], [-2]]);
+        assert_eq!(a.transpose()[(2, 1)], 6);
+        assert_eq!(a + a - a, a);
+
+        type Mod = ModInt<1_000_000_007>;
+        let (zero, one) = (Mod::new(0), Mod::new(1));
+        // F(10^18) mod 10^9+7.
+        assert_eq!(SMatrix::new([[one, one], [one, zero]]).pow(1_000_000_000_000_000_000)[(0, 1)].value(),
+                   209_783_453);
+    }
+
+    #[test]
+    fn test_floats() {
+        let rotate = Matrix::from_rows(vec![vec![0.0, -1.0], vec![1.0, 0.0]]).unwrap();
+        assert_eq!(rotate.pow(4).unwrap(), Matrix::identity(2));
+        assert_eq!(rotate.pow(2).unwrap(), Matrix::from_rows(vec![vec![-1.0, 0.0], vec![0.0, -1.0]]).unwrap());
+    }
+
+    /// A weighted digraph on 5 vertices, as `(from, to, weight)`.
+    const EDGES: [(usize, usize, i64); 8] =
+        [(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 5), (2, 3, 8), (3, 4, 3), (4, 0, -2), (3, 3, 1)];
+
+    /// The best walks of exactly `k` edges, by dynamic programming over `k`.
+    fn best_walks<T: Semiring>(weights: &Matrix<T>, k: u64) -> Matrix<T> {
+        let n = weights.rows();
+        let mut best: Matrix<T> = Matrix::identity(n);
+        for _ in 0..k {
+            let mut next: Matrix<T> = Matrix::zeros(n, n);
+            for (i, j, _) in EDGES.iter().cloned() {
+                for s in 0..n {
+                    next[(s, j)] = next[(s, j)].plus(&best[(s, i)].times(&weights[(i, j)]));
+                }
+            }
+            best = next;
+        }
+        best
+    }
+
+    #[test]
+    fn test_semirings() {
+        let mut shortest = Matrix::zeros(5, 5);
+        let mut longest = Matrix::zeros(5, 5);
+        let mut reachable = Matrix::zeros(5, 5);
+        for &(i, j, w) in &EDGES {
+            shortest[(i, j)] = MinPlus(w);
+            longest[(i, j)] = MaxPlus(w);
+            reachable[(i, j)] = true;
+        }
+        for k in 0..12 {
+            assert_eq!(shortest.pow(k).unwrap(), best_walks(&shortest, k), "min-plus, k = {}", k);
+            assert_eq!(longest.pow(k).unwrap(), best_walks(&longest, k), "max-plus, k = {}", k);
+            assert_eq!(reachable.pow(k).unwrap(), best_walks(&reachable, k), "boolean, k = {}", k);
+        }
+
+        // 0 -> 2 -> 1 -> 3 is the lightest three-edge walk to 3.
+        assert_eq!(shortest.pow(3).unwrap()[(0, 3)], MinPlus(8));
+        assert_eq!(shortest.pow(1).unwrap()[(1, 0)], MinPlus::INFINITY);
+        assert!(!reachable.pow(1).unwrap()[(0, 4)]);
+        assert!(reachable.pow(3).unwrap()[(0, 4)]);
+
+        let s = SMatrix::new([[MinPlus(1), MinPlus(5)], [MinPlus::INFINITY, MinPlus(2)]]);
+        assert_eq!(s.pow(3).into_rows(), [[MinPlus(3), MinPlus(7)], [MinPlus::INFINITY, MinPlus(6)]]);
+        // Finite weights whose sums overflow come out as no path, not a panic.
+        let heavy = SMatrix::new([[MinPlus(i64::MAX / 2)]]);
+        assert_eq!(heavy.pow(3).into_rows(), [[MinPlus::INFINITY]]);
+        assert_eq!(s.pow(0), SMatrix::identity());
+    }
+
+    #[bench]
+    fn bench_pow_min_plus(b: &mut Bencher) {
+        let mut seed = 3;
+        let weights = random(&mut seed, 30, 30);
+        let weights = Matrix::from_vec(30, 30, weights.as_slice().iter().map(|&w| MinPlus(w.abs())).collect())
+            .unwrap();
+        b.iter(|| weights.pow(1000).unwrap());
+    }
+}
@@ -0,0 +1,431 @@
+//! Integers modulo `m`.
+//!
+//! `ModInt<M>` fixes the modulus in the type, for the usual `10^9 + 7` style
+//! challenges; `DynModInt` carries it at runtime, for when it is part of the
+//! input. Both keep their value in `0..m`, so every operation is reduced by
+//! construction.
+
+use std::convert::TryFrom;
+use std::fmt::{Display, Error, Formatter};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use bigint::BigInt;
+use error::InputError;
+
+pub const MOD_1E9_7: u64 = 1_000_000_007;
+pub const MOD_998244353: u64 = 998_244_353;
+
+/// `(a + b) mod m`, for `a` and `b` already below `m`.
+pub fn add_mod(a: u64, b: u64, m: u64) -> u64 {
+    let (sum, overflow) = a.overflowing_add(b);
+    if overflow || sum >= m { sum.wrapping_sub(m) } else { sum }
+}
+
+/// `(a - b) mod m`, for `a` and `b` already below `m`.
+pub fn sub_mod(a: u64, b: u64, m: u64) -> u64 {
+    if a >= b { a - b } else { a.wrapping_sub(b).wrapping_add(m) }
+}
+
+pub fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+pub fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut acc = 1 % m;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = mul_mod(acc, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    acc
+}
+
+/// The inverse of `a` modulo `m`, if `a` and `m` are coprime. Works for any
+/// modulus, not just primes.
+pub fn inv_mod(a: u64, m: u64) -> Option<u64> {
+    // Extended Euclid, tracking only the coefficient of `a`.
+    let (mut r0, mut r1) = (m as i128, (a % m) as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    if r0 == 1 { Some(t0.rem_euclid(m as i128) as u64) } else { None }
+}
+
+/// Parse an integer of any size and reduce it modulo `m`.
+fn parse_mod(s: &str, m: u64) -> Result<u64, InputError> {
+    let n: BigInt = s.parse()?;
+    let mut r = n % BigInt::from(m);
+    if r.is_negative() {
+        r += BigInt::from(m);
+    }
+    Ok(u64::try_from(&r).unwrap())
+}
+
+/// An integer modulo the constant `M`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(n: u64) -> ModInt<M> {
+        ModInt(n % M)
+    }
+
+    pub fn modulus() -> u64 {
+        M
+    }
+
+    /// The representative in `0..M`.
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(self, exp: u64) -> ModInt<M> {
+        ModInt(pow_mod(self.0, exp, M))
+    }
+
+    /// The multiplicative inverse, if `self` is coprime to `M`.
+    pub fn inv(self) -> Option<ModInt<M>> {
+        inv_mod(self.0, M).map(ModInt)
+    }
+}
+
+macro_rules! impl_const_from {
+    (unsigned: $($u:ty)*; signed: $($i:ty)*) => {
+        $(
+            impl<const M: u64> From<$u> for ModInt<M> {
+                fn from(n: $u) -> ModInt<M> {
+                    ModInt((n as u128 % M as u128) as u64)
+                }
+            }
+        )*
+        $(
+            impl<const M: u64> From<$i> for ModInt<M> {
+                fn from(n: $i) -> ModInt<M> {
+                    ModInt((n as i128).rem_euclid(M as i128) as u64)
+                }
+            }
+        )*
+    };
+}
+
+impl_const_from!(unsigned: u8 u16 u32 u64 u128 usize; signed: i8 i16 i32 i64 i128 isize);
+
+impl<const M: u64> FromStr for ModInt<M> {
+    type Err = InputError;
+
+    /// An integer of any size, reduced modulo `M`.
+    fn from_str(s: &str) -> Result<ModInt<M>, InputError> {
+        parse_mod(s, M).map(ModInt)
+    }
+}
+
+impl<const M: u64> Display for ModInt<M> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = ModInt<M>;
+
+    fn add(self, rhs: ModInt<M>) -> ModInt<M> {
+        ModInt(add_mod(self.0, rhs.0, M))
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = ModInt<M>;
+
+    fn sub(self, rhs: ModInt<M>) -> ModInt<M> {
+        ModInt(sub_mod(self.0, rhs.0, M))
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = ModInt<M>;
+
+    fn mul(self, rhs: ModInt<M>) -> ModInt<M> {
+        ModInt(mul_mod(self.0, rhs.0, M))
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = ModInt<M>;
+
+    /// Multiplication by the inverse. Panics if `rhs` isn't invertible.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: ModInt<M>) -> ModInt<M> {
+        self * rhs.inv().expect("divisor is not invertible")
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = ModInt<M>;
+
+    fn neg(self) -> ModInt<M> {
+        ModInt(sub_mod(0, self.0, M))
+    }
+}
+
+/// An integer modulo a modulus chosen at runtime. Combining two values with
+/// different moduli panics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DynModInt {
+    value: u64,
+    modulus: u64,
+}
+
+impl DynModInt {
+    /// `n mod modulus`. Panics if `modulus` is zero.
+    pub fn new(n: u64, modulus: u64) -> DynModInt {
+        assert!(modulus > 0, "modulus must be positive");
+        DynModInt { value: n % modulus, modulus }
+    }
+
+    pub fn from_i64(n: i64, modulus: u64) -> DynModInt {
+        assert!(modulus > 0, "modulus must be positive");
+        DynModInt { value: (n as i128).rem_euclid(modulus as i128) as u64, modulus }
+    }
+
+    /// Parse an integer of any size and reduce it modulo `modulus`.
+    pub fn parse(s: &str, modulus: u64) -> Result<DynModInt, InputError> {
+        assert!(modulus > 0, "modulus must be positive");
+        parse_mod(s, modulus).map(|value| DynModInt { value, modulus })
+    }
+
+    pub fn modulus(self) -> u64 {
+        self.modulus
+    }
+
+    /// The representative in `0..modulus`.
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    pub fn pow(self, exp: u64) -> DynModInt {
+        DynModInt { value: pow_mod(self.value, exp, self.modulus), modulus: self.modulus }
+    }
+
+    /// The multiplicative inverse, if `self` is coprime to the modulus.
+    pub fn inv(self) -> Option<DynModInt> {
+        inv_mod(self.value, self.modulus).map(|value| DynModInt { value, modulus: self.modulus })
+    }
+
+    fn with(self, rhs: DynModInt, op: fn(u64, u64, u64) -> u64) -> DynModInt {
+        assert_eq!(self.modulus, rhs.modulus, "mismatched moduli");
+        DynModInt { value: op(self.value, rhs.value, self.modulus), modulus: self.modulus }
+    }
+}
+
+impl Display for DynModInt {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        Display::fmt(&self.value, f)
+    }
+}
+
+impl Add for DynModInt {
+    type Output = DynModInt;
+
+    fn add(self, rhs: DynModInt) -> DynModInt {
+        self.with(rhs, add_mod)
+    }
+}
+
+impl Sub for DynModInt {
+    type Output = DynModInt;
+
+    fn sub(self, rhs: DynModInt) -> DynModInt {
+        self.with(rhs, sub_mod)
+    }
+}
+
+impl Mul for DynModInt {
+    type Output = DynModInt;
+
+    fn mul(self, rhs: DynModInt) -> DynModInt {
+        self.with(rhs, mul_mod)
+    }
+}
+
+impl Div for DynModInt {
+    type Output = DynModInt;
+
+    /// Multiplication by the inverse. Panics if `rhs` isn't invertible.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: DynModInt) -> DynModInt {
+        self * rhs.inv().expect("divisor is not invertible")
+    }
+}
+
+impl Neg for DynModInt {
+    type Output = DynModInt;
+
+    fn neg(self) -> DynModInt {
+        DynModInt { value: sub_mod(0, self.value, self.modulus), modulus: self.modulus }
+    }
+}
+
+/// The assignment operators, in terms of the binary ones.
+macro_rules! impl_assign_ops {
+    ($(impl[$($gen:tt)*] $t:ty;)*) => {$(
+        impl<$($gen)*> AddAssign for $t {
+            fn add_assign(&mut self, rhs: $t) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<$($gen)*> SubAssign for $t {
+            fn sub_assign(&mut self, rhs: $t) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<$($gen)*> MulAssign for $t {
+            fn mul_assign(&mut self, rhs: $t) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<$($gen)*> DivAssign for $t {
+            fn div_assign(&mut self, rhs: $t) {
+                *self = *self / rhs;
+            }
+        }
+    )*};
+}
+
+impl_assign_ops! {
+    impl[const M: u64] ModInt<M>;
+    impl[] DynModInt;
+}
+
+// `DynModInt` can't know the modulus of an empty sum or product, so only
+// `ModInt` implements `Sum` and `Product`.
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = ModInt<M>>>(iter: I) -> ModInt<M> {
+        iter.fold(ModInt(0), |acc, n| acc + n)
+    }
+}
+
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = ModInt<M>>>(iter: I) -> ModInt<M> {
+        iter.fold(ModInt::new(1), |acc, n| acc * n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DynModInt, MOD_1E9_7, ModInt, inv_mod, pow_mod};
+
+    type Mod = ModInt<MOD_1E9_7>;
+
+    #[test]
+    fn test_arithmetic_reduces() {
+        let a = Mod::new(MOD_1E9_7 - 1);
+        let b = Mod::new(5);
+        assert_eq!((a + b).value(), 4);
+        assert_eq!((b - a).value(), 6);
+        assert_eq!((a * a).value(), 1);
+        assert_eq!((-b).value(), MOD_1E9_7 - 5);
+        assert_eq!(-Mod::new(0), Mod::new(0));
+        assert_eq!(Mod::from(-1i64), a);
+        assert_eq!(Mod::new(3 * MOD_1E9_7 + 2), Mod::new(2));
+    }
+
+    #[test]
+    fn test_large_modulus() {
+        const M: u64 = u64::MAX - 58;
+        let a = ModInt::<M>::new(M - 1);
+        assert_eq!((a + a).value(), M - 2);
+        assert_eq!((a * a).value(), 1);
+        assert_eq!(a.pow(M - 1).value(), 1);
+    }
+
+    #[test]
+    fn test_pow_inv_div() {
+        let b = Mod::new(3);
+        assert_eq!(b.pow(0), Mod::new(1));
+        assert_eq!(b.pow(MOD_1E9_7 - 1), Mod::new(1));
+        assert_eq!(b * b.inv().unwrap(), Mod::new(1));
+        assert_eq!(Mod::new(1) / Mod::new(2) * Mod::new(2), Mod::new(1));
+        assert_eq!(Mod::new(0).inv(), None);
+
+        // Composite moduli only invert units.
+        assert_eq!(ModInt::<12>::new(5).inv(), Some(ModInt::new(5)));
+        assert_eq!(ModInt::<12>::new(4).inv(), None);
+        assert_eq!(ModInt::<1>::new(7).pow(0).value(), 0);
+        for m in 2..60 {
+            for a in 0..m {
+                match inv_mod(a, m) {
+                    Some(x) => assert_eq!(a * x % m, 1),
+                    None => assert!((1..m).all(|x| a * x % m != 1)),
+                }
+            }
+        }
+        assert_eq!(pow_mod(2, 10, 1000), 24);
+    }
+
+    #[test]
+    #[should_panic(expected = "divisor is not invertible")]
+    fn test_div_by_zero() {
+        let _ = Mod::new(1) / Mod::new(0);
+    }
+
+    #[test]
+    fn test_parse_display() {
+        assert_eq!("1000000008".parse::<Mod>(), Ok(Mod::new(1)));
+        assert_eq!("-1".parse::<Mod>(), Ok(Mod::new(MOD_1E9_7 - 1)));
+        assert_eq!("123456789012345678901234567890".parse::<ModInt<97>>(),
+                   Ok(ModInt::new((123456789012345678901234567890u128 % 97) as u64)));
+        assert!("12a".parse::<Mod>().is_err());
+        assert_eq!(format!("{:>5}", ModInt::<7>::new(10)), "    3");
+    }
+
+    #[test]
+    fn test_sum_product_assign() {
+        let v: Vec<Mod> = (1..21).map(Mod::new).collect();
+        assert_eq!(v.iter().copied().sum::<Mod>(), Mod::new(210));
+        assert_eq!(v.iter().copied().product::<Mod>(), Mod::new((1..21u64).fold(1, |a, b| a * b % MOD_1E9_7)));
+
+        let mut x = Mod::new(10);
+        x += Mod::new(5);
+        x *= Mod::new(2);
+        x -= Mod::new(31);
+        x /= Mod::new(2);
+        assert_eq!(x, -Mod::new(1) / Mod::new(2));
+    }
+
+    #[test]
+    fn test_dyn_mod_int() {
+        let a = DynModInt::new(10, 7);
+        let b = DynModInt::from_i64(-1, 7);
+        assert_eq!((a.value(), b.value()), (3, 6));
+        assert_eq!((a + b).value(), 2);
+        assert_eq!((a - b).value(), 4);
+        assert_eq!((a * b).value(), 4);
+        assert_eq!((a / b).value(), 4);
+        assert_eq!(a.pow(6).value(), 1);
+        assert_eq!((-a).value(), 4);
+        assert_eq!(DynModInt::parse("100", 7).unwrap(), DynModInt::new(2, 7));
+        assert_eq!(a.to_string(), "3");
+
+        let mut c = a;
+        c *= a;
+        c += b;
+        assert_eq!(c.value(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "mismatched moduli")]
+    fn test_dyn_mismatched_moduli() {
+        let _ = DynModInt::new(1, 7) + DynModInt::new(1, 11);
+    }
+}
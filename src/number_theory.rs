@@ -0,0 +1,471 @@
+//! Primes, factorization and the arithmetic that goes with them.
+//!
+//! Everything works on `u64`. Products that might overflow go through
+//! `modint::mul_mod`, which widens to `u128`.
+
+use modint::{add_mod, mul_mod, pow_mod, sub_mod};
+
+pub use modint::inv_mod;
+
+/// The primes up to and including `n`, by the sieve of Eratosthenes.
+pub fn primes_up_to(n: u64) -> Vec<u64> {
+    let n = n as usize;
+    let mut composite = vec![false; n + 1];
+    let mut primes = vec![];
+    for i in 2..n + 1 {
+        if !composite[i] {
+            primes.push(i as u64);
+            let mut j = i * i;
+            while j <= n {
+                composite[j] = true;
+                j += i;
+            }
+        }
+    }
+    primes
+}
+
+/// The primes in `low..high`, sieved a block at a time, so memory is
+/// `O(√high)` however wide the range. Handy for ranges like
+/// `10^12..10^12 + 10^6` that are far too high to sieve from zero.
+pub fn primes_between(low: u64, high: u64) -> Vec<u64> {
+    const BLOCK: u64 = 1 << 16;
+    if high <= 2 {
+        return vec![];
+    }
+    let base = primes_up_to((high - 1).isqrt());
+    let mut primes = vec![];
+    let mut composite = Vec::with_capacity(BLOCK as usize);
+    let mut start = low.max(2);
+    while start < high {
+        let end = start.saturating_add(BLOCK).min(high);
+        composite.clear();
+        composite.resize((end - start) as usize, false);
+        for &p in base.iter().take_while(|&&p| p * p < end) {
+            let first = (p * p).max(start.div_ceil(p) * p);
+            for multiple in (first..end).step_by(p as usize) {
+                composite[(multiple - start) as usize] = true;
+            }
+        }
+        primes.extend((start..end).filter(|&n| !composite[(n - start) as usize]));
+        start = end;
+    }
+    primes
+}
+
+/// The smallest prime factor of every number up to a limit, by the linear
+/// sieve, which crosses each composite off exactly once and collects the
+/// primes on the way. Factorizes anything up to the limit in `O(log n)`.
+pub struct LinearSieve {
+    smallest: Vec<u32>,
+    primes: Vec<u64>,
+}
+
+impl LinearSieve {
+    /// Sieve up to and including `limit`, which must fit in a `u32`.
+    pub fn new(limit: u64) -> LinearSieve {
+        assert!(limit <= u32::MAX as u64, "sieve limit {} is too large", limit);
+        let n = limit as usize;
+        let mut smallest = vec![0u32; n + 1];
+        let mut primes = vec![];
+        for i in 2..n + 1 {
+            if smallest[i] == 0 {
+                smallest[i] = i as u32;
+                primes.push(i as u64);
+            }
+            // Each composite i * p is reached once, from its largest proper
+            // divisor i, with p no larger than i's smallest factor.
+            for &p in &primes {
+                let multiple = i * p as usize;
+                if p > smallest[i] as u64 || multiple > n {
+                    break;
+                }
+                smallest[multiple] = p as u32;
+            }
+        }
+        LinearSieve { smallest, primes }
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.smallest.len() as u64 - 1
+    }
+
+    /// The primes up to the limit, in order.
+    pub fn primes(&self) -> &[u64] {
+        &self.primes
+    }
+
+    pub fn is_prime(&self, n: u64) -> bool {
+        n >= 2 && self.smallest_factor(n) == n
+    }
+
+    /// The smallest prime factor of `n`, for `2 <= n <= limit`.
+    pub fn smallest_factor(&self, n: u64) -> u64 {
+        assert!(n >= 2 && n <= self.limit(), "{} is outside the sieve's 2..={}", n, self.limit());
+        self.smallest[n as usize] as u64
+    }
+
+    /// Prime factors of `n` and their exponents, smallest first.
+    pub fn factorize(&self, mut n: u64) -> Vec<(u64, u32)> {
+        let mut factors: Vec<(u64, u32)> = vec![];
+        while n > 1 {
+            let p = self.smallest_factor(n);
+            match factors.last_mut() {
+                Some(&mut (q, ref mut k)) if q == p => *k += 1,
+                _ => factors.push((p, 1)),
+            }
+            n /= p;
+        }
+        factors
+    }
+}
+
+/// The first twelve primes: as Miller–Rabin witnesses they're enough for
+/// every `n < 3.3 * 10^24`, so certainly every `u64`.
+const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Whether `n` is prime, by deterministic Miller–Rabin.
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for &p in &WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'witness: for &a in &WITNESSES {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// A proper factor of `n`, which must be composite with no factor below
+/// 100. Pollard's rho with Brent's batching of the gcds.
+fn pollard_rho(n: u64) -> u64 {
+    const BATCH: usize = 128;
+    for c in 1.. {
+        let f = |x: u64| add_mod(mul_mod(x, x, n), c, n);
+        let (mut x, mut y) = (2, 2);
+        loop {
+            let (x0, y0) = (x, y);
+            let mut product = 1;
+            for _ in 0..BATCH {
+                x = f(x);
+                y = f(f(y));
+                product = mul_mod(product, x.abs_diff(y), n);
+            }
+            let mut d = gcd(product, n);
+            if d == n {
+                // Some step in the batch hit a factor, or the cycle closed;
+                // replay it one step at a time to tell which.
+                let (mut x, mut y) = (x0, y0);
+                loop {
+                    x = f(x);
+                    y = f(f(y));
+                    d = gcd(x.abs_diff(y), n);
+                    if d != 1 {
+                        break;
+                    }
+                }
+            }
+            if d == n {
+                break;
+            }
+            if d != 1 {
+                return d;
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// Prime factors of `n` and their exponents, smallest first: trial division
+/// for the small ones, then Miller–Rabin and Pollard's rho. Panics if `n` is
+/// zero.
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    assert!(n > 0, "can't factorize zero");
+    let mut primes = vec![];
+    for p in 2..100 {
+        while n.is_multiple_of(p) {
+            primes.push(p);
+            n /= p;
+        }
+    }
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            primes.push(m);
+        } else {
+            let d = pollard_rho(m);
+            stack.push(d);
+            stack.push(m / d);
+        }
+    }
+    primes.sort_unstable();
+
+    let mut factors: Vec<(u64, u32)> = vec![];
+    for p in primes {
+        match factors.last_mut() {
+            Some(&mut (q, ref mut k)) if q == p => *k += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
+/// Euler's totient: how many of `1..=n` are coprime to `n`.
+pub fn totient(n: u64) -> u64 {
+    factorize(n).into_iter().fold(n, |phi, (p, _)| phi / p * (p - 1))
+}
+
+/// Every divisor of `n`, in increasing order.
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut divisors = vec![1];
+    for (p, k) in factorize(n) {
+        let smaller = divisors.len();
+        let mut power = 1;
+        for _ in 0..k {
+            power *= p;
+            for i in 0..smaller {
+                divisors.push(divisors[i] * power);
+            }
+        }
+    }
+    divisors.sort_unstable();
+    divisors
+}
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Panics if the result overflows.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b)).checked_mul(b).expect("lcm overflows u64")
+}
+
+/// `(g, x, y)` with `a x + b y = g = gcd(a, b)` and `g >= 0`.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 { (-r0, -x0, -y0) } else { (r0, x0, y0) }
+}
+
+/// The solution of `x ≡ r (mod m)` for every `(r, m)` in `congruences`, as
+/// `(x, l)` where `l` is the lcm of the moduli and `0 <= x < l`, or `None`
+/// if the congruences contradict each other.
+///
+/// The moduli needn't be coprime. Panics if one is zero, or if their lcm
+/// overflows a `u64`.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let (mut x, mut l) = (0u64, 1u64);
+    for &(r, m) in congruences {
+        assert!(m > 0, "modulus must be positive");
+        // Find t with x + l t ≡ r (mod m), i.e. l t ≡ r - x (mod m).
+        let g = gcd(l, m);
+        let diff = sub_mod(r % m, x % m, m);
+        if !diff.is_multiple_of(g) {
+            return None;
+        }
+        let step = m / g;
+        let inverse = inv_mod(l / g % step, step).unwrap();
+        let t = mul_mod(diff / g, inverse, step);
+        let next = (l as u128) * (step as u128);
+        assert!(next <= u64::MAX as u128, "lcm of the moduli overflows u64");
+        x = (x as u128 + l as u128 * t as u128) as u64;
+        l = next as u64;
+    }
+    Some((x, l))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LinearSieve, crt, divisors, ext_gcd, factorize, gcd, inv_mod, is_prime, primes_between,
+                primes_up_to, totient};
+    use test::Bencher;
+    use test_support::next_random;
+
+    fn is_prime_slow(n: u64) -> bool {
+        n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+    }
+
+    fn factorize_slow(mut n: u64) -> Vec<(u64, u32)> {
+        let mut factors = vec![];
+        let mut d = 2;
+        while d * d <= n {
+            let mut k = 0;
+            while n.is_multiple_of(d) {
+                n /= d;
+                k += 1;
+            }
+            if k > 0 {
+                factors.push((d, k));
+            }
+            d += 1;
+        }
+        if n > 1 {
+            factors.push((n, 1));
+        }
+        factors
+    }
+
+    /// Pseudo-random u64s below `bound`.
+    fn random(seed: &mut u64, bound: u64) -> u64 {
+        (next_random(seed) >> 11) % bound
+    }
+
+    #[test]
+    fn test_primes_up_to() {
+        assert_eq!(primes_up_to(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(primes_up_to(1), vec![]);
+    }
+
+    #[test]
+    fn test_sieves() {
+        let slow: Vec<u64> = (0..20_000).filter(|&n| is_prime_slow(n)).collect();
+        assert_eq!(primes_up_to(19_999), slow);
+        assert_eq!(primes_between(0, 20_000), slow);
+        for (low, high) in [(0, 3), (2, 3), (3, 3), (5000, 5001), (7919, 7920), (100, 200_000)] {
+            let expected: Vec<u64> = (low..high).filter(|&n| is_prime_slow(n)).collect();
+            assert_eq!(primes_between(low, high), expected, "{}..{}", low, high);
+        }
+        let far = 1_000_000_000_000;
+        let expected: Vec<u64> = (far..far + 2000).filter(|&n| is_prime(n)).collect();
+        assert_eq!(primes_between(far, far + 2000), expected);
+
+        let sieve = LinearSieve::new(20_000);
+        assert_eq!(sieve.primes(), &slow[..]);
+        assert_eq!(sieve.limit(), 20_000);
+        for n in 2..=20_000 {
+            assert_eq!(sieve.is_prime(n), is_prime_slow(n));
+            assert_eq!(sieve.factorize(n), factorize_slow(n), "{}", n);
+            assert_eq!(sieve.smallest_factor(n), factorize_slow(n)[0].0);
+        }
+        assert!(!sieve.is_prime(0) && !sieve.is_prime(1));
+        assert_eq!(sieve.factorize(1), vec![]);
+    }
+
+    #[test]
+    fn test_is_prime() {
+        for n in 0..50_000 {
+            assert_eq!(is_prime(n), is_prime_slow(n), "{}", n);
+        }
+        // Strong pseudoprimes to every prime base up to 7, 11, 13 and 23.
+        for &n in &[3_215_031_751, 2_152_302_898_747, 3_474_749_660_383, 3_825_123_056_546_413_051] {
+            assert!(!is_prime(n), "{}", n);
+        }
+        assert!(is_prime((1 << 61) - 1));
+        assert!(is_prime(u64::MAX - 58));
+        assert!(!is_prime(u64::MAX));
+    }
+
+    #[test]
+    fn test_factorize() {
+        let mut seed = 3;
+        for _ in 0..2000 {
+            let n = random(&mut seed, 1 << 24) + 1;
+            assert_eq!(factorize(n), factorize_slow(n), "{}", n);
+        }
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(factorize(4_294_967_291 * 4_294_967_279), vec![(4_294_967_279, 1), (4_294_967_291, 1)]);
+        assert_eq!(factorize(1 << 63), vec![(2, 63)]);
+        assert_eq!(factorize(999_983 * 999_983 * 999_979), vec![(999_979, 1), (999_983, 2)]);
+        for _ in 0..200 {
+            let n = random(&mut seed, u64::MAX) + 1;
+            let factors = factorize(n);
+            assert!(factors.iter().all(|&(p, _)| is_prime(p)));
+            assert_eq!(factors.iter().map(|&(p, k)| p.pow(k)).product::<u64>(), n);
+        }
+    }
+
+    #[test]
+    fn test_totient_and_divisors() {
+        for n in 1..2000u64 {
+            let coprime = (1..=n).filter(|&k| gcd(k, n) == 1).count() as u64;
+            assert_eq!(totient(n), coprime, "φ({})", n);
+            let expected: Vec<u64> = (1..=n).filter(|&d| n % d == 0).collect();
+            assert_eq!(divisors(n), expected, "divisors of {}", n);
+        }
+        assert_eq!(totient(1_000_000_007), 1_000_000_006);
+        assert_eq!(divisors(720_720).len(), 240);
+    }
+
+    #[test]
+    fn test_gcds_and_inverses() {
+        let mut seed = 9;
+        for _ in 0..2000 {
+            let a = random(&mut seed, 2_000_000) as i64 - 1_000_000;
+            let b = random(&mut seed, 2_000_000) as i64 - 1_000_000;
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(g as u64, gcd(a.unsigned_abs(), b.unsigned_abs()));
+        }
+        assert_eq!(ext_gcd(0, 0), (0, 1, 0));
+        assert_eq!(inv_mod(3, 10), Some(7));
+        assert_eq!(inv_mod(4, 10), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        for m1 in 1..13 {
+            for m2 in 1..13 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let l = m1 * m2 / gcd(m1, m2);
+                        let brute = (0..l).find(|x| x % m1 == r1 && x % m2 == r2);
+                        assert_eq!(crt(&[(r1, m1), (r2, m2)]), brute.map(|x| (x, l)),
+                                   "x = {} mod {}, {} mod {}", r1, m1, r2, m2);
+                    }
+                }
+            }
+        }
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 6), (3, 10), (13, 15)]), Some((13, 30)));
+        let big = [(1, 1_000_000_007), (2, 998_244_353), (3, 14)];
+        let (x, l) = crt(&big).unwrap();
+        assert_eq!(l, 1_000_000_007 * 998_244_353 * 14);
+        assert!(big.iter().all(|&(r, m)| x % m == r));
+
+        // Moduli past 2^63, where r + m would overflow.
+        let m = (1 << 63) + 3;
+        assert_eq!(crt(&[((1 << 63) + 2, m)]), Some(((1 << 63) + 2, m)));
+        assert_eq!(crt(&[(u64::MAX, m), (u64::MAX - m, m)]), Some((u64::MAX - m, m)));
+        assert_eq!(crt(&[(1 << 63, m), (5, m)]), None);
+        assert_eq!(crt(&[(0, 2), (u64::MAX - 2, u64::MAX / 2)]), Some(((1 << 63) - 2, u64::MAX - 1)));
+    }
+
+    #[bench]
+    fn bench_factorize_semiprime(b: &mut Bencher) {
+        b.iter(|| factorize(4_294_967_291 * 4_294_967_279));
+    }
+}
@@ -0,0 +1,395 @@
+//! Polynomials with coefficients modulo a prime, multiplied by the
+//! number-theoretic transform.
+//!
+//! The NTT is the FFT over `ModInt<M>`: it needs a `2^k`th root of unity,
+//! which exists when `2^k` divides `M - 1`. For 998244353 = 119 · 2^23 + 1
+//! that covers products of up to 2^23 coefficients. Other primes, like
+//! 10^9 + 7, get schoolbook multiplication instead, which is still correct,
+//! just quadratic.
+
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+
+use modint::ModInt;
+use number_theory::factorize;
+
+/// Below this many coefficients in the shorter factor, schoolbook
+/// multiplication beats three transforms.
+const NTT_THRESHOLD: usize = 32;
+
+/// A polynomial over `ModInt<M>` for a prime `M`, stored lowest degree
+/// first without trailing zeros, so the zero polynomial has no
+/// coefficients.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Polynomial<const M: u64> {
+    coeffs: Vec<ModInt<M>>,
+}
+
+impl<const M: u64> Polynomial<M> {
+    /// `coeffs[0] + coeffs[1] x + coeffs[2] x^2 + …`
+    pub fn new(mut coeffs: Vec<ModInt<M>>) -> Polynomial<M> {
+        while coeffs.last() == Some(&ModInt::new(0)) {
+            coeffs.pop();
+        }
+        Polynomial { coeffs }
+    }
+
+    pub fn zero() -> Polynomial<M> {
+        Polynomial { coeffs: vec![] }
+    }
+
+    pub fn one() -> Polynomial<M> {
+        Polynomial::new(vec![ModInt::new(1)])
+    }
+
+    /// The coefficients, lowest degree first.
+    pub fn coeffs(&self) -> &[ModInt<M>] {
+        &self.coeffs
+    }
+
+    /// The coefficient of `x^i`, zero past the degree.
+    pub fn coeff(&self, i: usize) -> ModInt<M> {
+        self.coeffs.get(i).cloned().unwrap_or_default()
+    }
+
+    /// The degree, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// The value at `x`, by Horner's rule.
+    pub fn eval(&self, x: ModInt<M>) -> ModInt<M> {
+        self.coeffs.iter().rev().fold(ModInt::new(0), |acc, &c| acc * x + c)
+    }
+
+    pub fn derivative(&self) -> Polynomial<M> {
+        let coeffs = self.coeffs.iter().enumerate().skip(1).map(|(i, &c)| c * ModInt::from(i)).collect();
+        Polynomial::new(coeffs)
+    }
+
+    /// `self mod x^n`: the terms below `x^n`.
+    pub fn truncate(&self, n: usize) -> Polynomial<M> {
+        Polynomial::new(self.coeffs[..n.min(self.coeffs.len())].to_vec())
+    }
+
+    /// The `g` with `self · g ≡ 1 (mod x^n)`, or `None` if the constant
+    /// term is zero. Newton's iteration doubles the correct terms each step:
+    /// `g ← g (2 - self · g)`.
+    pub fn inverse(&self, n: usize) -> Option<Polynomial<M>> {
+        let mut g = Polynomial::new(vec![self.coeff(0).inv()?]);
+        let mut len = 1;
+        while len < n {
+            len = (2 * len).min(n);
+            let error = (&self.truncate(len) * &g).truncate(len);
+            g = (&g * &(Polynomial::new(vec![ModInt::new(2)]) - error)).truncate(len);
+        }
+        Some(g.truncate(n))
+    }
+
+    /// `(q, r)` with `self = q · divisor + r` and `r` of lower degree than
+    /// `divisor`. Panics if `divisor` is zero.
+    ///
+    /// Reversing the coefficients turns division into multiplication by an
+    /// inverse mod `x^n`, so this costs a couple of multiplications.
+    pub fn div_rem(&self, divisor: &Polynomial<M>) -> (Polynomial<M>, Polynomial<M>) {
+        let d = divisor.degree().expect("division by zero");
+        let n = match self.degree() {
+            Some(deg) if deg >= d => deg - d + 1,
+            _ => return (Polynomial::zero(), self.clone()),
+        };
+        let reversed = |p: &Polynomial<M>| Polynomial::new(p.coeffs.iter().rev().cloned().collect());
+        let inverse = reversed(divisor).inverse(n).unwrap();
+        let mut q = (&reversed(self).truncate(n) * &inverse).coeffs;
+        q.resize(n, ModInt::new(0));
+        q.reverse();
+        let q = Polynomial::new(q);
+        let r = self - &(&q * divisor);
+        (q, r)
+    }
+
+    /// `self(inner(x)) mod x^n`, by Horner's rule with every intermediate
+    /// product truncated.
+    pub fn compose(&self, inner: &Polynomial<M>, n: usize) -> Polynomial<M> {
+        let inner = inner.truncate(n);
+        self.coeffs.iter().rev().fold(Polynomial::zero(), |acc, &c| {
+            (&acc * &inner + Polynomial::new(vec![c])).truncate(n)
+        })
+    }
+
+    fn zip_with<F>(&self, rhs: &Polynomial<M>, f: F) -> Polynomial<M>
+        where F: Fn(ModInt<M>, ModInt<M>) -> ModInt<M>
+    {
+        let len = self.coeffs.len().max(rhs.coeffs.len());
+        Polynomial::new((0..len).map(|i| f(self.coeff(i), rhs.coeff(i))).collect())
+    }
+}
+
+fn mul_naive<const M: u64>(a: &[ModInt<M>], b: &[ModInt<M>]) -> Vec<ModInt<M>> {
+    let mut product = vec![ModInt::new(0); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            product[i + j] += x * y;
+        }
+    }
+    product
+}
+
+/// The smallest generator of the multiplicative group modulo the prime `M`.
+fn primitive_root<const M: u64>() -> ModInt<M> {
+    let factors = factorize(M - 1);
+    (2..).map(ModInt::new)
+        .find(|g: &ModInt<M>| factors.iter().all(|&(p, _)| g.pow((M - 1) / p) != ModInt::new(1)))
+        .unwrap()
+}
+
+/// The transform of `a` in place, or its inverse. `a.len()` must be a power
+/// of two that divides `M - 1`.
+fn ntt<const M: u64>(a: &mut [ModInt<M>], root: ModInt<M>, invert: bool) {
+    let n = a.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let mut w = root.pow((M - 1) / len as u64);
+        if invert {
+            w = w.inv().unwrap();
+        }
+        for chunk in a.chunks_mut(len) {
+            let (low, high) = chunk.split_at_mut(len / 2);
+            let mut twiddle = ModInt::new(1);
+            for (u, v) in low.iter_mut().zip(high) {
+                let t = *v * twiddle;
+                *v = *u - t;
+                *u += t;
+                twiddle *= w;
+            }
+        }
+        len <<= 1;
+    }
+
+    if invert {
+        let scale = ModInt::from(n).inv().unwrap();
+        for x in a {
+            *x *= scale;
+        }
+    }
+}
+
+fn mul_ntt<const M: u64>(a: &[ModInt<M>], b: &[ModInt<M>]) -> Option<Vec<ModInt<M>>> {
+    let len = a.len() + b.len() - 1;
+    let size = len.next_power_of_two();
+    if !(M - 1).is_multiple_of(size as u64) {
+        return None;
+    }
+    let root = primitive_root::<M>();
+    let mut fa = a.to_vec();
+    let mut fb = b.to_vec();
+    fa.resize(size, ModInt::new(0));
+    fb.resize(size, ModInt::new(0));
+    ntt(&mut fa, root, false);
+    ntt(&mut fb, root, false);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x *= *y;
+    }
+    ntt(&mut fa, root, true);
+    fa.truncate(len);
+    Some(fa)
+}
+
+impl<const M: u64> Add<&Polynomial<M>> for &Polynomial<M> {
+    type Output = Polynomial<M>;
+
+    fn add(self, rhs: &Polynomial<M>) -> Polynomial<M> {
+        self.zip_with(rhs, |a, b| a + b)
+    }
+}
+
+impl<const M: u64> Sub<&Polynomial<M>> for &Polynomial<M> {
+    type Output = Polynomial<M>;
+
+    fn sub(self, rhs: &Polynomial<M>) -> Polynomial<M> {
+        self.zip_with(rhs, |a, b| a - b)
+    }
+}
+
+impl<const M: u64> Mul<&Polynomial<M>> for &Polynomial<M> {
+    type Output = Polynomial<M>;
+
+    fn mul(self, rhs: &Polynomial<M>) -> Polynomial<M> {
+        let (a, b) = (&self.coeffs, &rhs.coeffs);
+        if a.is_empty() || b.is_empty() {
+            return Polynomial::zero();
+        }
+        let product = if a.len().min(b.len()) < NTT_THRESHOLD {
+            mul_naive(a, b)
+        } else {
+            mul_ntt(a, b).unwrap_or_else(|| mul_naive(a, b))
+        };
+        Polynomial::new(product)
+    }
+}
+
+impl<const M: u64> Div<&Polynomial<M>> for &Polynomial<M> {
+    type Output = Polynomial<M>;
+
+    fn div(self, rhs: &Polynomial<M>) -> Polynomial<M> {
+        self.div_rem(rhs).0
+    }
+}
+
+impl<const M: u64> Rem<&Polynomial<M>> for &Polynomial<M> {
+    type Output = Polynomial<M>;
+
+    fn rem(self, rhs: &Polynomial<M>) -> Polynomial<M> {
+        self.div_rem(rhs).1
+    }
+}
+
+impl<const M: u64> Neg for Polynomial<M> {
+    type Output = Polynomial<M>;
+
+    fn neg(self) -> Polynomial<M> {
+        Polynomial { coeffs: self.coeffs.into_iter().map(|c| -c).collect() }
+    }
+}
+
+forward_binop!(impl[const M: u64] Polynomial<M>: Add, add, AddAssign, add_assign);
+forward_binop!(impl[const M: u64] Polynomial<M>: Sub, sub, SubAssign, sub_assign);
+forward_binop!(impl[const M: u64] Polynomial<M>: Mul, mul, MulAssign, mul_assign);
+forward_binop!(impl[const M: u64] Polynomial<M>: Div, div, DivAssign, div_assign);
+forward_binop!(impl[const M: u64] Polynomial<M>: Rem, rem, RemAssign, rem_assign);
+
+#[cfg(test)]
+mod tests {
+    use super::{Polynomial, mul_naive};
+    use modint::{MOD_1E9_7, MOD_998244353, ModInt};
+    use test::Bencher;
+    use test_support::next_random;
+
+    type Mod = ModInt<MOD_998244353>;
+    type Poly = Polynomial<MOD_998244353>;
+
+    fn poly(coeffs: &[u64]) -> Poly {
+        Polynomial::new(coeffs.iter().map(|&c| Mod::new(c)).collect())
+    }
+
+    /// A pseudo-random polynomial with `len` coefficients.
+    fn random<const M: u64>(seed: &mut u64, len: usize) -> Polynomial<M> {
+        Polynomial::new((0..len).map(|_| {
+            ModInt::new(next_random(seed) >> 16)
+        }).collect())
+    }
+
+    fn naive<const M: u64>(a: &Polynomial<M>, b: &Polynomial<M>) -> Polynomial<M> {
+        if a.is_zero() || b.is_zero() {
+            return Polynomial::zero();
+        }
+        Polynomial::new(mul_naive(a.coeffs(), b.coeffs()))
+    }
+
+    #[test]
+    fn test_basics() {
+        let p = poly(&[1, 2, 3, 0, 0]);
+        assert_eq!(p.coeffs(), &[Mod::new(1), Mod::new(2), Mod::new(3)][..]);
+        assert_eq!(p.degree(), Some(2));
+        assert_eq!(Poly::zero().degree(), None);
+        assert_eq!(p.eval(Mod::new(10)), Mod::new(321));
+        assert_eq!(p.derivative(), poly(&[2, 6]));
+        assert_eq!(p.coeff(7), Mod::new(0));
+        assert_eq!(&p + &poly(&[1, 1, MOD_998244353 - 3]), poly(&[2, 3]));
+        assert_eq!(p.clone() - p.clone(), Poly::zero());
+        assert_eq!(-p.clone() + p.clone(), Poly::zero());
+        assert_eq!(&p * &poly(&[1, 1]), poly(&[1, 3, 5, 3]));
+        assert_eq!(p.truncate(2), poly(&[1, 2]));
+    }
+
+    #[test]
+    fn test_mul_matches_naive() {
+        let mut seed = 1;
+        for &(m, n) in &[(1, 1), (31, 40), (32, 32), (33, 100), (100, 1000), (1000, 1000), (1, 2000), (513, 511)] {
+            let a: Poly = random(&mut seed, m);
+            let b: Poly = random(&mut seed, n);
+            assert_eq!(&a * &b, naive(&a, &b), "{} x {}", m, n);
+        }
+        assert_eq!(&random::<MOD_998244353>(&mut seed, 50) * &Poly::zero(), Poly::zero());
+
+        // 10^9 + 7 has no large power-of-two roots of unity.
+        let a: Polynomial<MOD_1E9_7> = random(&mut seed, 200);
+        let b: Polynomial<MOD_1E9_7> = random(&mut seed, 300);
+        assert_eq!(&a * &b, naive(&a, &b));
+    }
+
+    #[test]
+    fn test_inverse_and_division() {
+        let mut seed = 2;
+        for n in [1, 2, 3, 17, 64, 100, 700] {
+            let p: Poly = random(&mut seed, n + 5);
+            let inverse = p.inverse(n).unwrap();
+            assert_eq!((&p * &inverse).truncate(n), Poly::one(), "n = {}", n);
+            assert!(inverse.coeffs().len() <= n);
+        }
+        assert_eq!(poly(&[0, 1]).inverse(4), None);
+        // 1 / (1 - x) = 1 + x + x^2 + …
+        assert_eq!(poly(&[1, MOD_998244353 - 1]).inverse(5).unwrap(), poly(&[1, 1, 1, 1, 1]));
+
+        for &(m, n) in &[(10, 3), (3, 10), (100, 100), (1000, 37), (700, 300), (5, 1)] {
+            let a: Poly = random(&mut seed, m);
+            let b: Poly = random(&mut seed, n);
+            let (q, r) = a.div_rem(&b);
+            assert_eq!(&q * &b + &r, a, "{} / {}", m, n);
+            assert!(r.degree() < b.degree());
+            assert_eq!(&a / &b, q);
+            assert_eq!(a % b, r);
+        }
+        // x^2 - 1 = (x - 1)(x + 1)
+        let (q, r) = poly(&[MOD_998244353 - 1, 0, 1]).div_rem(&poly(&[1, 1]));
+        assert_eq!((q, r), (poly(&[MOD_998244353 - 1, 1]), Poly::zero()));
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn test_div_by_zero() {
+        let _ = poly(&[1, 2]) / Poly::zero();
+    }
+
+    #[test]
+    fn test_compose() {
+        let mut seed = 3;
+        for &(m, k, n) in &[(5, 3, 20), (20, 20, 30), (40, 60, 50), (1, 5, 3), (8, 1, 8)] {
+            let outer: Poly = random(&mut seed, m);
+            let inner: Poly = random(&mut seed, k);
+            // Σ c_i inner^i, with naive powers.
+            let mut expected = Poly::zero();
+            let mut power = Poly::one();
+            for &c in outer.coeffs() {
+                expected = &expected + &naive(&power, &Polynomial::new(vec![c]));
+                power = naive(&power, &inner).truncate(n);
+            }
+            assert_eq!(outer.compose(&inner, n), expected.truncate(n));
+        }
+        // (1 + x)^2 at x = 1 + 2y is (2 + 2y)^2.
+        assert_eq!(poly(&[1, 2, 1]).compose(&poly(&[1, 2]), 10), poly(&[4, 8, 4]));
+    }
+
+    #[bench]
+    fn bench_mul_65536(b: &mut Bencher) {
+        let mut seed = 4;
+        let x: Poly = random(&mut seed, 1 << 15);
+        let y: Poly = random(&mut seed, 1 << 15);
+        b.iter(|| &x * &y);
+    }
+}
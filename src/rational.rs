@@ -0,0 +1,338 @@
+//! Exact fractions.
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Error, Formatter};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use bigint::BigInt;
+use decimal::{Decimal, Rounding};
+use error::InputError;
+
+/// A fraction of `BigInt`s.
+///
+/// Always in lowest terms with a positive denominator, so the sign lives on
+/// the numerator and equal values have equal representations.
+#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+pub struct Rational {
+    numer: BigInt,
+    denom: BigInt,
+}
+
+impl Rational {
+    /// `numer / denom`, reduced. Panics if `denom` is zero.
+    pub fn new(numer: BigInt, denom: BigInt) -> Rational {
+        assert!(!denom.is_zero(), "zero denominator");
+        let mut gcd = numer.gcd(&denom);
+        if denom.is_negative() {
+            gcd = -gcd;
+        }
+        Rational { numer: numer / &gcd, denom: denom / gcd }
+    }
+
+    pub fn from_integer(n: BigInt) -> Rational {
+        Rational { numer: n, denom: BigInt::one() }
+    }
+
+    pub fn zero() -> Rational {
+        Rational::from_integer(BigInt::zero())
+    }
+
+    pub fn one() -> Rational {
+        Rational::from_integer(BigInt::one())
+    }
+
+    pub fn numer(&self) -> &BigInt {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &BigInt {
+        &self.denom
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.numer.is_negative()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == BigInt::one()
+    }
+
+    pub fn abs(&self) -> Rational {
+        Rational { numer: self.numer.abs(), denom: self.denom.clone() }
+    }
+
+    /// `1 / self`. Panics if `self` is zero.
+    pub fn recip(&self) -> Rational {
+        Rational::new(self.denom.clone(), self.numer.clone())
+    }
+
+    pub fn pow(&self, exp: i32) -> Rational {
+        let base = if exp < 0 { self.recip() } else { self.clone() };
+        let exp = exp.unsigned_abs();
+        Rational { numer: base.numer.pow(exp), denom: base.denom.pow(exp) }
+    }
+
+    /// The largest integer not greater than `self`.
+    pub fn floor(&self) -> BigInt {
+        let (q, r) = self.numer.div_rem(&self.denom);
+        if r.is_negative() { q - BigInt::one() } else { q }
+    }
+
+    /// The smallest integer not less than `self`.
+    pub fn ceil(&self) -> BigInt {
+        -(-self).floor()
+    }
+
+    /// `self` rounded to `places` decimal places, with ties going to the even
+    /// digit as `printf` does. A result that rounds to zero has no sign.
+    pub fn to_decimal_string(&self, places: usize) -> String {
+        Decimal::from_rational(self, places as u32, Rounding::HalfEven).to_string()
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Rational {
+        Rational::zero()
+    }
+}
+
+impl From<BigInt> for Rational {
+    fn from(n: BigInt) -> Rational {
+        Rational::from_integer(n)
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($($t:ty)*) => {$(
+        impl From<$t> for Rational {
+            fn from(n: $t) -> Rational {
+                Rational::from_integer(BigInt::from(n))
+            }
+        }
+    )*};
+}
+
+impl_from_primitive!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+impl FromStr for Rational {
+    type Err = InputError;
+
+    /// A fraction like `-3/4`, or a decimal like `-0.75`, `12` or `2.5e-3`.
+    fn from_str(s: &str) -> Result<Rational, InputError> {
+        if let Some(slash) = s.find('/') {
+            let numer = s[..slash].parse()?;
+            let denom: BigInt = s[slash + 1..].parse().map_err(|err: InputError| {
+                err.offset(1, slash + 2)
+            })?;
+            if denom.is_zero() {
+                return Err(InputError::new(s, "a fraction").because("zero denominator"));
+            }
+            return Ok(Rational::new(numer, denom));
+        }
+        Ok(s.parse::<Decimal>()?.to_rational())
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (&self.numer * &other.denom).cmp(&(&other.numer * &self.denom))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational { numer: -self.numer, denom: self.denom }
+    }
+}
+
+impl Neg for &Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational { numer: -&self.numer, denom: self.denom.clone() }
+    }
+}
+
+impl Add<&Rational> for &Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: &Rational) -> Rational {
+        Rational::new(&self.numer * &rhs.denom + &rhs.numer * &self.denom, &self.denom * &rhs.denom)
+    }
+}
+
+impl Sub<&Rational> for &Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: &Rational) -> Rational {
+        Rational::new(&self.numer * &rhs.denom - &rhs.numer * &self.denom, &self.denom * &rhs.denom)
+    }
+}
+
+impl Mul<&Rational> for &Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: &Rational) -> Rational {
+        Rational::new(&self.numer * &rhs.numer, &self.denom * &rhs.denom)
+    }
+}
+
+impl Div<&Rational> for &Rational {
+    type Output = Rational;
+
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: &Rational) -> Rational {
+        Rational::new(&self.numer * &rhs.denom, &self.denom * &rhs.numer)
+    }
+}
+
+forward_binop!(Rational: Add, add, AddAssign, add_assign);
+forward_binop!(Rational: Sub, sub, SubAssign, sub_assign);
+forward_binop!(Rational: Mul, mul, MulAssign, mul_assign);
+forward_binop!(Rational: Div, div, DivAssign, div_assign);
+
+impl Sum for Rational {
+    fn sum<I: Iterator<Item = Rational>>(iter: I) -> Rational {
+        iter.fold(Rational::zero(), |acc, n| acc + n)
+    }
+}
+
+impl<'a> Sum<&'a Rational> for Rational {
+    fn sum<I: Iterator<Item = &'a Rational>>(iter: I) -> Rational {
+        iter.fold(Rational::zero(), |acc, n| acc + n)
+    }
+}
+
+impl Product for Rational {
+    fn product<I: Iterator<Item = Rational>>(iter: I) -> Rational {
+        iter.fold(Rational::one(), |acc, n| acc * n)
+    }
+}
+
+impl<'a> Product<&'a Rational> for Rational {
+    fn product<I: Iterator<Item = &'a Rational>>(iter: I) -> Rational {
+        iter.fold(Rational::one(), |acc, n| acc * n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rational;
+    use bigint::BigInt;
+
+    fn ratio(numer: i64, denom: i64) -> Rational {
+        Rational::new(BigInt::from(numer), BigInt::from(denom))
+    }
+
+    #[test]
+    fn test_new_reduces() {
+        let r = ratio(6, -8);
+        assert_eq!((r.numer(), r.denom()), (&BigInt::from(-3), &BigInt::from(4)));
+        assert_eq!(ratio(0, -5), Rational::zero());
+        assert_eq!(ratio(-7, -7), Rational::one());
+    }
+
+    #[test]
+    #[should_panic(expected = "zero denominator")]
+    fn test_zero_denominator() {
+        ratio(1, 0);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(ratio(1, 2) + ratio(1, 3), ratio(5, 6));
+        assert_eq!(ratio(1, 2) - ratio(1, 3), ratio(1, 6));
+        assert_eq!(&ratio(2, 3) * &ratio(9, 4), ratio(3, 2));
+        assert_eq!(ratio(2, 3) / ratio(-4, 9), ratio(-3, 2));
+        assert_eq!(-ratio(1, 2), ratio(-1, 2));
+        assert_eq!(ratio(2, 3).pow(-3), ratio(27, 8));
+        assert_eq!(ratio(2, 3).pow(0), Rational::one());
+
+        let mut r = ratio(1, 4);
+        r += ratio(1, 4);
+        r *= &ratio(4, 1);
+        r -= Rational::one();
+        assert_eq!(r, Rational::one());
+
+        let harmonic: Rational = (1..11).map(|n| ratio(1, n)).sum();
+        assert_eq!(harmonic, ratio(7381, 2520));
+        let product: Rational = (1..11).map(|n| ratio(n, n + 1)).product();
+        assert_eq!(product, ratio(1, 11));
+    }
+
+    #[test]
+    fn test_floor_ceil_ord() {
+        assert_eq!(ratio(7, 2).floor(), BigInt::from(3));
+        assert_eq!(ratio(-7, 2).floor(), BigInt::from(-4));
+        assert_eq!(ratio(-7, 2).ceil(), BigInt::from(-3));
+        assert_eq!(ratio(4, 2).ceil(), BigInt::from(2));
+
+        let mut v = vec![ratio(1, 2), ratio(-2, 3), ratio(1, 3), ratio(2, 3), ratio(-1, 2)];
+        v.sort();
+        assert_eq!(v, vec![ratio(-2, 3), ratio(-1, 2), ratio(1, 3), ratio(1, 2), ratio(2, 3)]);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("-3/6".parse(), Ok(ratio(-1, 2)));
+        assert_eq!("3/-6".parse(), Ok(ratio(-1, 2)));
+        assert_eq!("-0.75".parse(), Ok(ratio(-3, 4)));
+        assert_eq!("+.5".parse(), Ok(ratio(1, 2)));
+        assert_eq!("12.".parse(), Ok(ratio(12, 1)));
+        assert_eq!("0.000".parse(), Ok(Rational::zero()));
+        assert_eq!("2.5e-3".parse(), Ok(ratio(1, 400)));
+
+        let err = "1/2x".parse::<Rational>().unwrap_err();
+        assert_eq!((err.line, err.column), (Some(1), Some(4)));
+        let err = "1.2.3".parse::<Rational>().unwrap_err();
+        assert_eq!(err.column, Some(4));
+        assert!("1/0".parse::<Rational>().is_err());
+        assert!(".".parse::<Rational>().is_err());
+        assert!("--1".parse::<Rational>().is_err());
+        assert!("".parse::<Rational>().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(ratio(-6, 8).to_string(), "-3/4");
+        assert_eq!(ratio(10, 5).to_string(), "2");
+    }
+
+    #[test]
+    fn test_to_decimal_string() {
+        assert_eq!(ratio(1, 3).to_decimal_string(3), "0.333");
+        assert_eq!(ratio(2, 3).to_decimal_string(3), "0.667");
+        assert_eq!(ratio(1, 6).to_decimal_string(6), "0.166667");
+        assert_eq!(ratio(-22, 7).to_decimal_string(4), "-3.1429");
+        assert_eq!(ratio(5, 2).to_decimal_string(0), "2");
+        assert_eq!(ratio(7, 2).to_decimal_string(0), "4");
+        assert_eq!(ratio(1, 16).to_decimal_string(3), "0.062");
+        assert_eq!(ratio(3, 16).to_decimal_string(3), "0.188");
+        assert_eq!(ratio(-1, 1000).to_decimal_string(2), "0.00");
+        assert_eq!(ratio(123, 1).to_decimal_string(2), "123.00");
+    }
+}
@@ -0,0 +1,259 @@
+//! Linear recurrences: far-off terms by Kitamasa's method, and the shortest
+//! recurrence that generates a sequence by Berlekamp–Massey.
+
+use std::mem;
+
+use matrix::ShapeError;
+use modint::ModInt;
+use ring::Semiring;
+
+/// The sequence `a(n) = c[0] a(n-1) + c[1] a(n-2) + ... + c[d-1] a(n-d)`
+/// with initial terms `a(0), ..., a(d-1)`.
+///
+/// Fibonacci is `new(vec![1, 1], vec![0, 1])`; tribonacci has three ones.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinearRecurrence<T> {
+    coeffs: Vec<T>,
+    initial: Vec<T>,
+}
+
+impl<T: Semiring> LinearRecurrence<T> {
+    /// Needs exactly one initial term per coefficient.
+    pub fn new(coeffs: Vec<T>, initial: Vec<T>) -> Result<LinearRecurrence<T>, ShapeError> {
+        if coeffs.len() != initial.len() {
+            return Err(ShapeError::Length { expected: coeffs.len(), len: initial.len() });
+        }
+        Ok(LinearRecurrence { coeffs, initial })
+    }
+
+    /// The number of earlier terms each term depends on.
+    pub fn degree(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    pub fn initial(&self) -> &[T] {
+        &self.initial
+    }
+
+    /// The first `count` terms, one after another.
+    pub fn terms(&self, count: usize) -> Vec<T> {
+        let mut terms: Vec<T> = self.initial.iter().take(count).cloned().collect();
+        while terms.len() < count {
+            let n = terms.len();
+            let next = self.coeffs.iter().enumerate()
+                .fold(T::zero(), |acc, (i, c)| acc.plus(&c.times(&terms[n - 1 - i])));
+            terms.push(next);
+        }
+        terms
+    }
+
+    /// `a(n)`, in `O(d² log n)` operations.
+    ///
+    /// Kitamasa's method: every `a(n)` is a fixed combination of the initial
+    /// terms, given by the coefficients of `x^n` reduced modulo the
+    /// characteristic polynomial `x^d - c[0] x^(d-1) - ... - c[d-1]`.
+    pub fn nth(&self, mut n: u64) -> T {
+        let d = self.degree();
+        if n < d as u64 {
+            return self.initial[n as usize].clone();
+        }
+        if d == 0 {
+            return T::zero();
+        }
+
+        let mut acc = vec![T::zero(); d];
+        acc[0] = T::one();
+        let mut base = vec![T::zero(); d];
+        if d == 1 {
+            base[0] = self.coeffs[0].clone();
+        } else {
+            base[1] = T::one();
+        }
+        while n > 0 {
+            if n & 1 == 1 {
+                acc = self.mul_reduced(&acc, &base);
+            }
+            n >>= 1;
+            if n > 0 {
+                base = self.mul_reduced(&base, &base);
+            }
+        }
+        acc.iter().zip(&self.initial).fold(T::zero(), |sum, (r, a)| sum.plus(&r.times(a)))
+    }
+
+    /// The product of two polynomials of degree below `d`, reduced modulo
+    /// the characteristic polynomial.
+    fn mul_reduced(&self, a: &[T], b: &[T]) -> Vec<T> {
+        let d = self.degree();
+        let mut product = vec![T::zero(); 2 * d - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                product[i + j] = product[i + j].plus(&x.times(y));
+            }
+        }
+        // x^k = c[0] x^(k-1) + ... + c[d-1] x^(k-d), from the top down.
+        for k in (d..product.len()).rev() {
+            let top = mem::replace(&mut product[k], T::zero());
+            for (i, c) in self.coeffs.iter().enumerate() {
+                product[k - 1 - i] = product[k - 1 - i].plus(&top.times(c));
+            }
+        }
+        product.truncate(d);
+        product
+    }
+}
+
+impl<const M: u64> LinearRecurrence<ModInt<M>> {
+    /// The shortest recurrence that generates `terms`, by Berlekamp–Massey.
+    /// `M` must be prime.
+    ///
+    /// A recurrence of degree `d` is pinned down by its first `2d` terms;
+    /// with fewer, this still returns a recurrence that fits, but it may not
+    /// be the one that produced them.
+    pub fn find(terms: &[ModInt<M>]) -> LinearRecurrence<ModInt<M>> {
+        let zero = ModInt::new(0);
+        // The connection polynomial `1 - c[0] x - ... - c[l-1] x^l`, and the
+        // one from before the last length change, with its discrepancy.
+        let mut current = vec![ModInt::new(1)];
+        let mut previous = vec![ModInt::new(1)];
+        let mut previous_discrepancy = ModInt::new(1);
+        let mut len = 0;
+        let mut shift = 1;
+
+        for n in 0..terms.len() {
+            let discrepancy = (0..=len).map(|i| current[i] * terms[n - i]).sum::<ModInt<M>>();
+            if discrepancy == zero {
+                shift += 1;
+                continue;
+            }
+            let scale = discrepancy / previous_discrepancy;
+            let before = current.clone();
+            if current.len() < previous.len() + shift {
+                current.resize(previous.len() + shift, zero);
+            }
+            for (i, &p) in previous.iter().enumerate() {
+                current[i + shift] -= scale * p;
+            }
+            if 2 * len <= n {
+                len = n + 1 - len;
+                previous = before;
+                previous_discrepancy = discrepancy;
+                shift = 1;
+            } else {
+                shift += 1;
+            }
+        }
+
+        current.resize(len + 1, zero);
+        LinearRecurrence {
+            coeffs: current[1..].iter().map(|&c| -c).collect(),
+            initial: terms[..len].to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LinearRecurrence;
+    use bigint::BigInt;
+    use matrix::ShapeError;
+    use modint::{MOD_1E9_7, MOD_998244353, ModInt};
+    use ring::MinPlus;
+    use scanner::Scanner;
+    use test::Bencher;
+    use test_support::next_random;
+
+    type Mod = ModInt<MOD_1E9_7>;
+
+    fn mods(v: &[u64]) -> Vec<Mod> {
+        v.iter().map(|&n| Mod::new(n)).collect()
+    }
+
+    /// Pseudo-random residues.
+    fn random(seed: &mut u64, len: usize) -> Vec<Mod> {
+        (0..len).map(|_| {
+            Mod::new(next_random(seed) >> 20)
+        }).collect()
+    }
+
+    #[test]
+    fn test_nth_matches_terms() {
+        let fib = LinearRecurrence::new(vec![1i64, 1], vec![0, 1]).unwrap();
+        assert_eq!(fib.terms(10), [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+        assert_eq!(fib.nth(90), 2_880_067_194_370_816_120);
+
+        let tribonacci = LinearRecurrence::new(vec![1i64; 3], vec![0, 0, 1]).unwrap();
+        assert_eq!(tribonacci.terms(10), [0, 0, 1, 1, 2, 4, 7, 13, 24, 44]);
+
+        let mut seed = 5;
+        for d in 0..8 {
+            let r = LinearRecurrence::new(random(&mut seed, d), random(&mut seed, d)).unwrap();
+            let terms = r.terms(100);
+            for (n, &term) in terms.iter().enumerate() {
+                assert_eq!(r.nth(n as u64), term, "degree {}, term {}", d, n);
+            }
+        }
+
+        let big = LinearRecurrence::new(vec![BigInt::one(); 2], vec![BigInt::zero(), BigInt::one()]).unwrap();
+        assert_eq!(big.nth(300).to_string(), "222232244629420445529739893461909967206666939096499764990979600");
+
+        // The cheapest way to climb n stairs in steps of 1 (cost 3) or 2 (cost 5).
+        let cost = LinearRecurrence::new(vec![MinPlus(3), MinPlus(5)], vec![MinPlus(0), MinPlus(3)]).unwrap();
+        assert_eq!(cost.nth(1001), MinPlus(500 * 5 + 3));
+
+        assert_eq!(LinearRecurrence::new(vec![1], vec![]), Err(ShapeError::Length { expected: 1, len: 0 }));
+    }
+
+    #[test]
+    fn test_fibonacci_fixture() {
+        let input = include_str!("../tests/fibonacci-finding-easy/input01.txt");
+        let output = include_str!("../tests/fibonacci-finding-easy/output01.txt");
+        let mut scan = Scanner::new(input.as_bytes());
+        let count: usize = scan.read().unwrap();
+        let expected: Vec<u64> = output.lines().map(|line| line.parse().unwrap()).collect();
+        assert_eq!(expected.len(), count);
+
+        for want in expected {
+            let (f0, f1, n): (Mod, Mod, u64) = scan.tuple().unwrap();
+            let r = LinearRecurrence::new(mods(&[1, 1]), vec![f0, f1]).unwrap();
+            assert_eq!(r.nth(n).value(), want);
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let fib = LinearRecurrence::new(mods(&[1, 1]), mods(&[0, 1])).unwrap();
+        assert_eq!(LinearRecurrence::find(&fib.terms(20)), fib);
+
+        // Squares satisfy a(n) = 3a(n-1) - 3a(n-2) + a(n-3).
+        let squares: Vec<Mod> = (0..10u64).map(|n| Mod::new(n * n)).collect();
+        let found = LinearRecurrence::find(&squares);
+        assert_eq!(found.coeffs(), &[Mod::new(3), -Mod::new(3), Mod::new(1)][..]);
+        assert_eq!(found.nth(1_000_000), Mod::new(1_000_000_000_000));
+
+        let mut seed = 11;
+        for d in 1..12 {
+            let r = LinearRecurrence::new(random(&mut seed, d), random(&mut seed, d)).unwrap();
+            let terms = r.terms(2 * d + 5);
+            let found = LinearRecurrence::find(&terms);
+            assert!(found.degree() <= d);
+            assert_eq!(found.terms(terms.len()), terms, "degree {}", d);
+            assert_eq!(found.nth(1 << 40), r.nth(1 << 40));
+        }
+
+        assert_eq!(LinearRecurrence::find(&mods(&[0, 0, 0])).degree(), 0);
+        let other: Vec<ModInt<MOD_998244353>> = (0..8u64).map(|n| ModInt::new(1 << n)).collect();
+        assert_eq!(LinearRecurrence::find(&other).coeffs(), &[ModInt::new(2)][..]);
+    }
+
+    #[bench]
+    fn bench_nth_degree_50(b: &mut Bencher) {
+        let mut seed = 1;
+        let r = LinearRecurrence::new(random(&mut seed, 50), random(&mut seed, 50)).unwrap();
+        b.iter(|| r.nth(1_000_000_000_000_000_000));
+    }
+}
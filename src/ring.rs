@@ -0,0 +1,188 @@
+//! The arithmetic that generic numeric code, like `Matrix`, is written
+//! against.
+
+use std::ops::{Add, Mul, Sub};
+
+use bigint::BigInt;
+use modint::ModInt;
+use rational::Rational;
+
+/// A type with an associative, commutative "addition" and an associative
+/// "multiplication" that distributes over it, each with an identity.
+///
+/// The operations are named `plus` and `times` rather than borrowing the
+/// operators, so `MinPlus` can add by taking a minimum and `bool` can
+/// multiply with `&&`.
+pub trait Semiring: Clone + PartialEq {
+    /// The identity for `plus`, which annihilates under `times`.
+    fn zero() -> Self;
+    /// The identity for `times`.
+    fn one() -> Self;
+    fn plus(&self, rhs: &Self) -> Self;
+    fn times(&self, rhs: &Self) -> Self;
+}
+
+/// A semiring whose `plus` and `times` are the `+` and `*` operators, and
+/// which also has `-`.
+///
+/// The operators take their operands by value, so generic code clones
+/// anything it needs to keep.
+pub trait Ring: Semiring + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {}
+
+macro_rules! impl_ring {
+    ($($t:ty: $zero:expr, $one:expr;)*) => {$(
+        impl Semiring for $t {
+            fn zero() -> $t {
+                $zero
+            }
+
+            fn one() -> $t {
+                $one
+            }
+
+            fn plus(&self, rhs: &$t) -> $t {
+                self.clone() + rhs.clone()
+            }
+
+            fn times(&self, rhs: &$t) -> $t {
+                self.clone() * rhs.clone()
+            }
+        }
+
+        impl Ring for $t {}
+    )*};
+}
+
+impl_ring! {
+    i32: 0, 1;
+    i64: 0, 1;
+    i128: 0, 1;
+    f64: 0.0, 1.0;
+    BigInt: BigInt::zero(), BigInt::one();
+    Rational: Rational::zero(), Rational::one();
+}
+
+impl<const M: u64> Semiring for ModInt<M> {
+    fn zero() -> ModInt<M> {
+        ModInt::new(0)
+    }
+
+    fn one() -> ModInt<M> {
+        ModInt::new(1)
+    }
+
+    fn plus(&self, rhs: &ModInt<M>) -> ModInt<M> {
+        *self + *rhs
+    }
+
+    fn times(&self, rhs: &ModInt<M>) -> ModInt<M> {
+        *self * *rhs
+    }
+}
+
+impl<const M: u64> Ring for ModInt<M> {}
+
+/// Reachability: `plus` is `||` and `times` is `&&`, so the `k`th power of
+/// an adjacency matrix says which vertices are joined by a walk of exactly
+/// `k` edges.
+impl Semiring for bool {
+    fn zero() -> bool {
+        false
+    }
+
+    fn one() -> bool {
+        true
+    }
+
+    fn plus(&self, rhs: &bool) -> bool {
+        *self || *rhs
+    }
+
+    fn times(&self, rhs: &bool) -> bool {
+        *self && *rhs
+    }
+}
+
+/// The tropical semiring for shortest paths: `plus` is `min` and `times` is
+/// `+`, so the `k`th power of a matrix of edge weights holds the lightest
+/// walks of exactly `k` edges.
+///
+/// `i64::MAX` is infinity, meaning no path, and absorbs anything added to it.
+/// Finite sums that would overflow saturate to infinity too.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MinPlus(pub i64);
+
+impl MinPlus {
+    pub const INFINITY: MinPlus = MinPlus(i64::MAX);
+}
+
+impl Semiring for MinPlus {
+    fn zero() -> MinPlus {
+        MinPlus::INFINITY
+    }
+
+    fn one() -> MinPlus {
+        MinPlus(0)
+    }
+
+    fn plus(&self, rhs: &MinPlus) -> MinPlus {
+        MinPlus(self.0.min(rhs.0))
+    }
+
+    fn times(&self, rhs: &MinPlus) -> MinPlus {
+        if *self == MinPlus::INFINITY || *rhs == MinPlus::INFINITY {
+            MinPlus::INFINITY
+        } else {
+            MinPlus(self.0.saturating_add(rhs.0))
+        }
+    }
+}
+
+/// `MinPlus` turned around for longest paths: `plus` is `max`, and
+/// `i64::MIN` is negative infinity. Sums saturate at either end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MaxPlus(pub i64);
+
+impl MaxPlus {
+    pub const NEG_INFINITY: MaxPlus = MaxPlus(i64::MIN);
+}
+
+impl Semiring for MaxPlus {
+    fn zero() -> MaxPlus {
+        MaxPlus::NEG_INFINITY
+    }
+
+    fn one() -> MaxPlus {
+        MaxPlus(0)
+    }
+
+    fn plus(&self, rhs: &MaxPlus) -> MaxPlus {
+        MaxPlus(self.0.max(rhs.0))
+    }
+
+    fn times(&self, rhs: &MaxPlus) -> MaxPlus {
+        if *self == MaxPlus::NEG_INFINITY || *rhs == MaxPlus::NEG_INFINITY {
+            MaxPlus::NEG_INFINITY
+        } else {
+            MaxPlus(self.0.saturating_add(rhs.0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MaxPlus, MinPlus, Semiring};
+
+    #[test]
+    fn test_tropical_saturates() {
+        assert_eq!(MinPlus(i64::MAX - 1).times(&MinPlus(5)), MinPlus::INFINITY);
+        assert_eq!(MinPlus(i64::MAX - 10).times(&MinPlus(5)), MinPlus(i64::MAX - 5));
+        assert_eq!(MinPlus::INFINITY.times(&MinPlus(-5)), MinPlus::INFINITY);
+        assert_eq!(MinPlus(i64::MIN + 1).times(&MinPlus(-5)), MinPlus(i64::MIN));
+
+        assert_eq!(MaxPlus(i64::MIN + 1).times(&MaxPlus(-5)), MaxPlus::NEG_INFINITY);
+        assert_eq!(MaxPlus::NEG_INFINITY.times(&MaxPlus(5)), MaxPlus::NEG_INFINITY);
+        assert_eq!(MaxPlus(i64::MAX - 1).times(&MaxPlus(5)), MaxPlus(i64::MAX));
+        assert_eq!(MaxPlus(i64::MAX - 10).times(&MaxPlus(5)), MaxPlus(i64::MAX - 5));
+    }
+}
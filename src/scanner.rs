@@ -0,0 +1,345 @@
+use std::{io, str};
+use std::io::BufRead;
+
+use error::{InputError, type_name};
+
+/// Reads whitespace- or line-delimited values from a buffered reader.
+///
+/// Tokens may span line boundaries, so `read` works the same whether a
+/// challenge puts its numbers on one line or many. Parse failures are
+/// reported as an `InputError` pointing at the offending line and column.
+pub struct Scanner<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+    pos: usize,
+    start: usize,
+}
+
+impl Scanner<io::StdinLock<'static>> {
+    pub fn stdin() -> Scanner<io::StdinLock<'static>> {
+        Scanner::new(io::stdin().lock())
+    }
+}
+
+impl<R: BufRead> Scanner<R> {
+    pub fn new(reader: R) -> Scanner<R> {
+        Scanner { reader, line: String::new(), line_number: 0, pos: 0, start: 0 }
+    }
+
+    /// Replace the current line with the next one from the reader. Returns
+    /// false at end of input.
+    fn fill_line(&mut self) -> Result<bool, InputError> {
+        self.line.clear();
+        self.pos = 0;
+        self.start = 0;
+        if self.reader.read_line(&mut self.line)? > 0 {
+            self.line_number += 1;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// The byte range of the next token on the current line, if any.
+    fn next_span(&self) -> Option<(usize, usize)> {
+        let rest = &self.line[self.pos..];
+        let trimmed = rest.trim_start();
+        if trimmed.is_empty() {
+            return None;
+        }
+        let start = self.pos + rest.len() - trimmed.len();
+        let len = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+        Some((start, start + len))
+    }
+
+    /// The 1-based column of a byte offset into the current line.
+    fn column(&self, offset: usize) -> usize {
+        self.line[..offset].chars().count() + 1
+    }
+
+    fn end_of_input<T>(&self) -> InputError {
+        let mut err = InputError::end_of_input(type_name::<T>());
+        err.line = Some(self.line_number);
+        err
+    }
+
+    /// Parse `text` as a `T`, locating any error relative to `(line, column)`.
+    fn parse<T>(text: &str, line: usize, column: usize) -> Result<T, InputError>
+        where T: str::FromStr,
+              T::Err: Into<InputError>,
+    {
+        text.parse().map_err(|err: T::Err| {
+            err.into().or_token::<T>(text).offset(line, column)
+        })
+    }
+
+    /// Move past the next token, reading more lines as needed, and return
+    /// its byte range within the current line.
+    fn advance(&mut self) -> Result<Option<(usize, usize)>, InputError> {
+        loop {
+            if let Some((start, end)) = self.next_span() {
+                self.start = start;
+                self.pos = end;
+                return Ok(Some((start, end)));
+            }
+            if !self.fill_line()? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// The line and column where the last token read starts, for errors
+    /// found after parsing it.
+    pub fn position(&self) -> (usize, usize) {
+        (self.line_number, self.column(self.start))
+    }
+
+    /// The next whitespace-delimited token, or `None` at end of input.
+    pub fn token(&mut self) -> Result<Option<&str>, InputError> {
+        Ok(self.advance()?.map(move |(start, end)| &self.line[start..end]))
+    }
+
+    pub fn read<T>(&mut self) -> Result<T, InputError>
+        where T: str::FromStr,
+              T::Err: Into<InputError>,
+    {
+        match self.advance()? {
+            Some((start, end)) => {
+                Self::parse(&self.line[start..end], self.line_number, self.column(start))
+            },
+            None => Err(self.end_of_input::<T>()),
+        }
+    }
+
+    /// The rest of the current line, or the whole next line if nothing but
+    /// whitespace is left on this one. Surrounding whitespace is trimmed.
+    pub fn line<T>(&mut self) -> Result<T, InputError>
+        where T: str::FromStr,
+              T::Err: Into<InputError>,
+    {
+        if self.line[self.pos..].trim().is_empty() && !self.fill_line()? {
+            return Err(self.end_of_input::<T>());
+        }
+        let rest = &self.line[self.pos..];
+        let start = self.pos + rest.len() - rest.trim_start().len();
+        let value = Self::parse(rest.trim(), self.line_number, self.column(start));
+        self.pos = self.line.len();
+        value
+    }
+
+    pub fn lines<T>(&mut self, count: usize) -> Result<Vec<T>, InputError>
+        where T: str::FromStr,
+              T::Err: Into<InputError>,
+    {
+        (0..count).map(|_| self.line()).collect()
+    }
+
+    /// Parse the next `count` lines as one value, e.g. a grid. The lines are
+    /// trimmed and joined with `'\n'`; errors are still placed by where each
+    /// line started before trimming.
+    pub fn block<T>(&mut self, count: usize) -> Result<T, InputError>
+        where T: str::FromStr,
+              T::Err: Into<InputError>,
+    {
+        let mut text = String::new();
+        let mut starts = vec![];
+        for i in 0..count {
+            if self.line[self.pos..].trim().is_empty() && !self.fill_line()? {
+                return Err(self.end_of_input::<T>());
+            }
+            let rest = &self.line[self.pos..];
+            let start = self.pos + rest.len() - rest.trim_start().len();
+            starts.push((self.line_number, self.column(start)));
+            if i > 0 {
+                text.push('\n');
+            }
+            text.push_str(rest.trim());
+            self.pos = self.line.len();
+        }
+        text.parse().map_err(|err: T::Err| {
+            let err = err.into().or_token::<T>(&text);
+            match err.line.and_then(|l| l.checked_sub(1)).and_then(|i| starts.get(i)) {
+                Some(&(line, column)) => InputError {
+                    line: Some(line),
+                    column: err.column.map(|c| column + c - 1),
+                    ..err
+                },
+                None => {
+                    let (line, column) = starts.first().cloned().unwrap_or((self.line_number + 1, 1));
+                    err.offset(line, column)
+                },
+            }
+        })
+    }
+
+    pub fn tuple<T: FromTokens>(&mut self) -> Result<T, InputError> {
+        T::from_tokens(self)
+    }
+
+    pub fn vec<T>(&mut self, len: usize) -> Result<Vec<T>, InputError>
+        where T: str::FromStr,
+              T::Err: Into<InputError>,
+    {
+        (0..len).map(|_| self.read()).collect()
+    }
+
+    /// A vector preceded by its length, as most challenges format arrays.
+    pub fn prefixed_vec<T>(&mut self) -> Result<Vec<T>, InputError>
+        where T: str::FromStr,
+              T::Err: Into<InputError>,
+    {
+        let len = self.read()?;
+        self.vec(len)
+    }
+
+    pub fn matrix<T>(&mut self, rows: usize, cols: usize) -> Result<Vec<Vec<T>>, InputError>
+        where T: str::FromStr,
+              T::Err: Into<InputError>,
+    {
+        (0..rows).map(|_| self.vec(cols)).collect()
+    }
+}
+
+/// Values made of several consecutive tokens, such as tuples.
+pub trait FromTokens: Sized {
+    fn from_tokens<R: BufRead>(scanner: &mut Scanner<R>) -> Result<Self, InputError>;
+}
+
+macro_rules! tuple_from_tokens {
+    ($($name:ident),+) => {
+        impl<$($name),+> FromTokens for ($($name,)+)
+            where $($name: str::FromStr, $name::Err: Into<InputError>),+
+        {
+            fn from_tokens<R: BufRead>(scanner: &mut Scanner<R>) -> Result<Self, InputError> {
+                Ok(($(scanner.read::<$name>()?,)+))
+            }
+        }
+    }
+}
+
+tuple_from_tokens!(A);
+tuple_from_tokens!(A, B);
+tuple_from_tokens!(A, B, C);
+tuple_from_tokens!(A, B, C, D);
+tuple_from_tokens!(A, B, C, D, E);
+
+#[cfg(test)]
+mod tests {
+    use super::Scanner;
+    use error::InputError;
+
+    fn scanner(input: &str) -> Scanner<&[u8]> {
+        Scanner::new(input.as_bytes())
+    }
+
+    #[test]
+    fn test_tokens_span_lines() {
+        let mut scan = scanner("1 2\n\n  3\t4\n");
+        let nums: Vec<i32> = scan.vec(4).unwrap();
+        assert_eq!(nums, vec![1, 2, 3, 4]);
+        assert_eq!(scan.token().unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_mixed_types() {
+        let mut scan = scanner("42 -7 hello 2.5");
+        assert_eq!(scan.read::<u64>().unwrap(), 42);
+        assert_eq!(scan.read::<i8>().unwrap(), -7);
+        assert_eq!(scan.read::<String>().unwrap(), "hello");
+        assert_eq!(scan.read::<f64>().unwrap(), 2.5);
+    }
+
+    #[test]
+    fn test_line() {
+        let mut scan = scanner("3\np--\n-m- \n");
+        assert_eq!(scan.read::<usize>().unwrap(), 3);
+        assert_eq!(scan.line::<String>().unwrap(), "p--");
+        assert_eq!(scan.line::<String>().unwrap(), "-m-");
+    }
+
+    #[test]
+    fn test_line_remainder() {
+        let mut scan = scanner("9 6 2015\n");
+        assert_eq!(scan.read::<i32>().unwrap(), 9);
+        assert_eq!(scan.line::<String>().unwrap(), "6 2015");
+    }
+
+    #[test]
+    fn test_block() {
+        let mut scan = scanner("2\n ab\ncd\nef\n");
+        assert_eq!(scan.read::<usize>().unwrap(), 2);
+        assert_eq!(scan.block::<String>(2).unwrap(), "ab\ncd");
+        assert_eq!(scan.line::<String>().unwrap(), "ef");
+    }
+
+    #[test]
+    fn test_tuple() {
+        let mut scan = scanner("2 3 1\n9 1 7\n");
+        assert_eq!(scan.tuple::<(i64, i64, i64)>().unwrap(), (2, 3, 1));
+        assert_eq!(scan.tuple::<(u8, String, i32)>().unwrap(), (9, "1".to_string(), 7));
+    }
+
+    #[test]
+    fn test_prefixed_vec() {
+        let mut scan = scanner("3\n10 20 30\n");
+        assert_eq!(scan.prefixed_vec::<i32>().unwrap(), vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn test_matrix() {
+        let mut scan = scanner("11 2 4\n4 5 6\n10 8 -12\n");
+        let m: Vec<Vec<i32>> = scan.matrix(3, 3).unwrap();
+        assert_eq!(m, vec![vec![11, 2, 4], vec![4, 5, 6], vec![10, 8, -12]]);
+    }
+
+    #[test]
+    fn test_parse_error_position() {
+        let mut scan = scanner("1 2\n3  x4 5\n");
+        let err = scan.vec::<i32>(5).unwrap_err();
+        assert_eq!(err.line, Some(2));
+        assert_eq!(err.column, Some(4));
+        assert_eq!(err.token, Some("x4".to_string()));
+        assert_eq!(err.expected, Some("i32".to_string()));
+        assert_eq!(err.reason, Some("invalid digit found in string".to_string()));
+    }
+
+    #[test]
+    fn test_end_of_input() {
+        let err = scanner("1\n").vec::<i32>(2).unwrap_err();
+        let mut expected = InputError::end_of_input("i32");
+        expected.line = Some(1);
+        assert_eq!(err, expected);
+    }
+
+    #[test]
+    fn test_nested_error_position() {
+        #[derive(Debug)]
+        struct Cells;
+        impl ::std::str::FromStr for Cells {
+            type Err = InputError;
+            fn from_str(s: &str) -> Result<Cells, InputError> {
+                for (y, line) in s.lines().enumerate() {
+                    if let Some(x) = line.find('?') {
+                        return Err(InputError::new("?", "cell").at(y + 1, x + 1));
+                    }
+                }
+                Ok(Cells)
+            }
+        }
+
+        let mut scan = scanner("2\n  --\n-?\n");
+        scan.read::<usize>().unwrap();
+        let err = scan.block::<Cells>(2).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(3), Some(2)));
+
+        let mut scan = scanner("--\n   -?\n");
+        let err = scan.block::<Cells>(2).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(5)));
+
+        let mut scan = scanner("1 \t?-\n");
+        scan.read::<usize>().unwrap();
+        let err = scan.block::<Cells>(1).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(1), Some(4)));
+    }
+}
@@ -0,0 +1,14 @@
+//! Helpers shared by the unit tests.
+
+/// The next value from a linear congruential generator: plenty random for
+/// test fixtures, and the same on every run. Use the high bits; the low
+/// ones cycle quickly.
+pub fn next_random(seed: &mut u64) -> u64 {
+    *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    *seed
+}
+
+/// Four draws' worth of high bits, for 128-bit fixtures.
+pub fn next_random_u128(seed: &mut u64) -> u128 {
+    (0..4).fold(0, |acc, _| acc << 32 | (next_random(seed) >> 32) as u128)
+}
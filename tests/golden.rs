@@ -0,0 +1,64 @@
+//! Runs every `[[bin]]` in Cargo.toml against the fixtures in
+//! `tests/<challenge>/`, comparing its stdout to the expected output.
+
+extern crate hackerrank;
+
+use std::{env, fs};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use hackerrank::golden;
+
+/// Binaries are built next to the `deps` directory this test runs from.
+fn bin_dir() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    exe.parent().and_then(Path::parent).unwrap().to_path_buf()
+}
+
+fn run(bin: &Path, input: &[u8]) -> Result<String, String> {
+    let mut child = Command::new(bin)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("could not start {}: {}", bin.display(), err))?;
+    child.stdin.take().unwrap().write_all(input).map_err(|err| err.to_string())?;
+    let output = child.wait_with_output().map_err(|err| err.to_string())?;
+    if !output.status.success() {
+        return Err(format!("exited with {}\n{}", output.status,
+                           String::from_utf8_lossy(&output.stderr)));
+    }
+    String::from_utf8(output.stdout).map_err(|err| err.to_string())
+}
+
+#[test]
+fn golden_files() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let manifest = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+    let bin_dir = bin_dir();
+
+    let mut checked = 0;
+    let mut failures = vec![];
+    for name in golden::bin_names(&manifest) {
+        let bin = bin_dir.join(format!("{}{}", name, env::consts::EXE_SUFFIX));
+        for fixture in golden::fixtures(&root.join("tests").join(&name)).unwrap() {
+            let input = fs::read(&fixture.input).unwrap();
+            let expected = fs::read_to_string(&fixture.output).unwrap();
+            let report = match run(&bin, &input) {
+                Ok(actual) => golden::diff(&expected, &actual),
+                Err(err) => Some(err),
+            };
+            if let Some(report) = report {
+                failures.push(format!("{} (input{}.txt):\n{}", name, fixture.name, report));
+            }
+            checked += 1;
+        }
+    }
+
+    assert!(checked > 0, "no fixtures found");
+    if !failures.is_empty() {
+        panic!("{} of {} golden tests failed\n\n{}",
+               failures.len(), checked, failures.join("\n"));
+    }
+}
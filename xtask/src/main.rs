@@ -0,0 +1,39 @@
+//! Project maintenance commands, run with `cargo xtask <command>`.
+
+use std::{env, process};
+use std::path::{Path, PathBuf};
+
+mod manifest;
+mod new_challenge;
+
+const USAGE: &str = "\
+Usage: cargo xtask <command>
+
+Commands:
+    new-challenge CATEGORY/NAME    Scaffold a solution, its Cargo.toml entry
+                                   and golden-test fixtures";
+
+/// The root of the main project, one level above this crate.
+fn project_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    match args.iter().map(|s| &s[..]).collect::<Vec<&str>>()[..] {
+        ["new-challenge", spec] => {
+            let root = project_root();
+            let path = new_challenge::new_challenge(&root, spec)?;
+            println!("created {}", path.strip_prefix(&root).unwrap_or(&path).display());
+            Ok(())
+        },
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(err) = run(&args) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,222 @@
+//! Just enough of a Cargo.toml parser to manage `[[bin]]` tables.
+//!
+//! Everything that isn't a `[[bin]]` table is kept verbatim. The `[[bin]]`
+//! tables are always written last, sorted by category and then name.
+
+use std::{fmt, str};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Bin {
+    pub name: String,
+    pub path: String,
+    /// Any other keys in the table, kept as written.
+    pub extra: Vec<String>,
+}
+
+impl Bin {
+    pub fn new(name: &str, path: &str) -> Bin {
+        Bin { name: name.to_string(), path: path.to_string(), extra: vec![] }
+    }
+
+    /// The directory of the solution under `src/`, like `algorithms/warmup`.
+    pub fn category(&self) -> &str {
+        let path = self.path.trim_start_matches("src/");
+        match path.rfind('/') {
+            Some(i) => &path[..i],
+            None => "",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Manifest {
+    /// Lines outside of `[[bin]]` tables.
+    pub other: Vec<String>,
+    pub bins: Vec<Bin>,
+}
+
+impl Manifest {
+    pub fn find(&self, name: &str) -> Option<&Bin> {
+        self.bins.iter().find(|b| b.name == name)
+    }
+
+    /// Add a binary, refusing names already used in any category.
+    pub fn add_bin(&mut self, bin: Bin) -> Result<(), String> {
+        if let Some(existing) = self.find(&bin.name) {
+            return Err(format!("a binary named '{}' already exists at '{}'",
+                               bin.name, existing.path));
+        }
+        self.bins.push(bin);
+        self.sort();
+        Ok(())
+    }
+
+    pub fn sort(&mut self) {
+        self.bins.sort_by(|a, b| (a.category(), &a.name).cmp(&(b.category(), &b.name)));
+    }
+}
+
+fn parse_string(value: &str, line_number: usize) -> Result<String, String> {
+    let value = value.trim();
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        Ok(value[1..value.len() - 1].to_string())
+    } else {
+        Err(format!("line {}: expected a quoted string, found {}", line_number, value))
+    }
+}
+
+/// A `[[bin]]` table that is still being read.
+struct BinTable {
+    line_number: usize,
+    name: Option<String>,
+    path: Option<String>,
+    extra: Vec<String>,
+}
+
+impl BinTable {
+    fn new(line_number: usize) -> BinTable {
+        BinTable { line_number, name: None, path: None, extra: vec![] }
+    }
+
+    fn add_line(&mut self, line: &str, line_number: usize) -> Result<(), String> {
+        let trimmed = line.trim();
+        let mut parts = trimmed.splitn(2, '=');
+        match (parts.next().map(str::trim), parts.next()) {
+            (Some("name"), Some(value)) => self.name = Some(parse_string(value, line_number)?),
+            (Some("path"), Some(value)) => self.path = Some(parse_string(value, line_number)?),
+            _ if trimmed.is_empty() => {},
+            _ => self.extra.push(line.to_string()),
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Bin, String> {
+        match (self.name, self.path) {
+            (Some(name), Some(path)) => Ok(Bin { name, path, extra: self.extra }),
+            _ => Err(format!("line {}: [[bin]] needs a name and a path", self.line_number)),
+        }
+    }
+}
+
+impl str::FromStr for Manifest {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Manifest, String> {
+        let mut other = vec![];
+        let mut bins = vec![];
+        let mut current: Option<BinTable> = None;
+
+        for (i, line) in s.lines().enumerate() {
+            let line_number = i + 1;
+            if line.trim().starts_with('[') {
+                if let Some(table) = current.take() {
+                    bins.push(table.finish()?);
+                }
+                if line.trim() == "[[bin]]" {
+                    current = Some(BinTable::new(line_number));
+                    continue;
+                }
+            }
+            match current {
+                Some(ref mut table) => table.add_line(line, line_number)?,
+                None => other.push(line.to_string()),
+            }
+        }
+        if let Some(table) = current {
+            bins.push(table.finish()?);
+        }
+
+        while other.last().is_some_and(|l| l.trim().is_empty()) {
+            other.pop();
+        }
+        Ok(Manifest { other, bins })
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in &self.other {
+            writeln!(f, "{}", line)?;
+        }
+        for bin in &self.bins {
+            writeln!(f)?;
+            writeln!(f, "[[bin]]")?;
+            writeln!(f, "name = \"{}\"", bin.name)?;
+            writeln!(f, "path = \"{}\"", bin.path)?;
+            for line in &bin.extra {
+                writeln!(f, "{}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bin, Manifest};
+
+    const MANIFEST: &str = "\
+[package]
+name = \"hackerrank\"
+
+[workspace]
+members = [\"xtask\"]
+
+[[bin]]
+name = \"solve-me-first\"
+path = \"src/algorithms/warmup/solve-me-first.rs\"
+
+[[bin]]
+name = \"saveprincess\"
+path = \"src/ai/bot-building/saveprincess.rs\"
+test = false
+
+[[bin]]
+name = \"utopian-tree\"
+path = \"src/algorithms/implementation/utopian-tree.rs\"
+";
+
+    #[test]
+    fn test_parse() {
+        let manifest: Manifest = MANIFEST.parse().unwrap();
+        assert_eq!(manifest.other[0], "[package]");
+        assert_eq!(manifest.other.last().unwrap(), "members = [\"xtask\"]");
+        assert_eq!(manifest.bins.len(), 3);
+        assert_eq!(manifest.bins[1].name, "saveprincess");
+        assert_eq!(manifest.bins[1].category(), "ai/bot-building");
+        assert_eq!(manifest.bins[1].extra, vec!["test = false"]);
+    }
+
+    #[test]
+    fn test_round_trip_sorted() {
+        let mut manifest: Manifest = MANIFEST.parse().unwrap();
+        manifest.sort();
+        let names: Vec<&str> = manifest.bins.iter().map(|b| &b.name[..]).collect();
+        assert_eq!(names, vec!["saveprincess", "utopian-tree", "solve-me-first"]);
+
+        let reparsed: Manifest = manifest.to_string().parse().unwrap();
+        assert_eq!(reparsed, manifest);
+    }
+
+    #[test]
+    fn test_add_bin() {
+        let mut manifest: Manifest = MANIFEST.parse().unwrap();
+        manifest.add_bin(Bin::new("staircase", "src/algorithms/warmup/staircase.rs")).unwrap();
+        let names: Vec<&str> = manifest.bins.iter().map(|b| &b.name[..]).collect();
+        assert_eq!(names, vec!["saveprincess", "utopian-tree", "solve-me-first", "staircase"]);
+    }
+
+    #[test]
+    fn test_add_duplicate_in_other_category() {
+        let mut manifest: Manifest = MANIFEST.parse().unwrap();
+        let err = manifest.add_bin(Bin::new("utopian-tree", "src/algorithms/warmup/utopian-tree.rs"))
+            .unwrap_err();
+        assert!(err.contains("src/algorithms/implementation/utopian-tree.rs"));
+    }
+
+    #[test]
+    fn test_missing_path() {
+        let err = "[[bin]]\nname = \"x\"\n".parse::<Manifest>().unwrap_err();
+        assert_eq!(err, "line 1: [[bin]] needs a name and a path");
+    }
+}
@@ -0,0 +1,172 @@
+//! Scaffolding for a new challenge: a solution wired to the shared scanner,
+//! a `[[bin]]` entry in Cargo.toml and empty golden-test fixtures.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use manifest::{Bin, Manifest};
+
+/// Split `algorithms/warmup/staircase` into its category and name, checking
+/// that every part is a plain lowercase slug.
+pub fn parse_challenge(spec: &str) -> Result<(String, String), String> {
+    let spec = spec.trim_matches('/');
+    let valid = |part: &str| {
+        !part.is_empty() &&
+            part.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    };
+    if !spec.split('/').all(valid) {
+        return Err(format!("'{}' should look like 'category/sub-category/name'", spec));
+    }
+    match spec.rfind('/') {
+        Some(i) => Ok((spec[..i].to_string(), spec[i + 1..].to_string())),
+        None => Err(format!("'{}' needs a category, like 'algorithms/warmup/{}'", spec, spec)),
+    }
+}
+
+pub fn template(name: &str) -> String {
+    format!("\
+// https://www.hackerrank.com/challenges/{name}
+extern crate hackerrank;
+
+use std::io::BufRead;
+
+use hackerrank::{{InputError, Scanner}};
+
+fn solve<R: BufRead>(scan: &mut Scanner<R>) -> Result<String, InputError> {{
+    let n: i64 = scan.read()?;
+    Ok(n.to_string())
+}}
+
+#[cfg_attr(test, allow(dead_code))]
+fn main() {{
+    hackerrank::run(|scan| {{
+        println!(\"{{}}\", solve(scan)?);
+        Ok(())
+    }});
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::solve;
+    use hackerrank::Scanner;
+
+    #[test]
+    fn test_sample() {{
+        let mut scan = Scanner::new(\"0\\n\".as_bytes());
+        assert_eq!(solve(&mut scan).unwrap(), \"0\");
+    }}
+}}
+", name = name)
+}
+
+/// Create the solution, fixtures and manifest entry for a challenge under
+/// the project at `root`. Returns the path of the new solution.
+pub fn new_challenge(root: &Path, spec: &str) -> Result<PathBuf, String> {
+    let (category, name) = parse_challenge(spec)?;
+    let relative_path = format!("src/{}/{}.rs", category, name);
+    let source_path = root.join(&relative_path);
+    if source_path.exists() {
+        return Err(format!("'{}' already exists", relative_path));
+    }
+
+    let manifest_path = root.join("Cargo.toml");
+    let manifest_text = fs::read_to_string(&manifest_path)
+        .map_err(|err| format!("could not read {}: {}", manifest_path.display(), err))?;
+    let mut manifest: Manifest = manifest_text.parse()
+        .map_err(|err| format!("could not parse {}: {}", manifest_path.display(), err))?;
+    manifest.add_bin(Bin::new(&name, &relative_path))?;
+
+    let fixture_dir = root.join("tests").join(&name);
+    let write = |path: &Path, contents: &str| {
+        fs::write(path, contents)
+            .map_err(|err| format!("could not write {}: {}", path.display(), err))
+    };
+    let create_dir = |path: &Path| {
+        fs::create_dir_all(path)
+            .map_err(|err| format!("could not create {}: {}", path.display(), err))
+    };
+
+    create_dir(source_path.parent().unwrap())?;
+    write(&source_path, &template(&name))?;
+    create_dir(&fixture_dir)?;
+    for file in &["input00.txt", "output00.txt"] {
+        let path = fixture_dir.join(file);
+        if !path.exists() {
+            write(&path, "")?;
+        }
+    }
+    write(&manifest_path, &manifest.to_string())?;
+
+    Ok(source_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+    use std::path::PathBuf;
+
+    use super::{new_challenge, parse_challenge};
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("xtask-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Cargo.toml"), "\
+[package]
+name = \"hackerrank\"
+
+[[bin]]
+name = \"utopian-tree\"
+path = \"src/algorithms/implementation/utopian-tree.rs\"
+").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_challenge() {
+        assert_eq!(parse_challenge("algorithms/warmup/staircase").unwrap(),
+                   ("algorithms/warmup".to_string(), "staircase".to_string()));
+        assert!(parse_challenge("staircase").is_err());
+        assert!(parse_challenge("algorithms/Warmup/staircase").is_err());
+        assert!(parse_challenge("algorithms//staircase").is_err());
+        assert!(parse_challenge("../etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_new_challenge() {
+        let root = scratch_dir("new");
+        let path = new_challenge(&root, "algorithms/warmup/staircase").unwrap();
+
+        assert_eq!(path, root.join("src/algorithms/warmup/staircase.rs"));
+        let source = fs::read_to_string(&path).unwrap();
+        assert!(source.starts_with("// https://www.hackerrank.com/challenges/staircase\n"));
+        assert_eq!(fs::read_to_string(root.join("tests/staircase/input00.txt")).unwrap(), "");
+        assert!(root.join("tests/staircase/output00.txt").is_file());
+
+        let manifest = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(manifest.ends_with("\
+[[bin]]
+name = \"utopian-tree\"
+path = \"src/algorithms/implementation/utopian-tree.rs\"
+
+[[bin]]
+name = \"staircase\"
+path = \"src/algorithms/warmup/staircase.rs\"
+"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_refuses_duplicates() {
+        let root = scratch_dir("duplicate");
+        let before = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+
+        let err = new_challenge(&root, "algorithms/warmup/utopian-tree").unwrap_err();
+        assert!(err.contains("already exists"));
+        assert!(!root.join("src/algorithms/warmup/utopian-tree.rs").exists());
+        assert_eq!(fs::read_to_string(root.join("Cargo.toml")).unwrap(), before);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}